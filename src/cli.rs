@@ -0,0 +1,234 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::tidal::{Desc, ListFilter, MergeOptions, PullNumber, Refs, Verdict};
+
+#[derive(Parser, Debug)]
+#[command(author, version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[clap(flatten)]
+    pub create: CreateArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create a pull request (the default without a subcommand)
+    #[command(disable_help_flag = true)]
+    Create(CreateArgs),
+
+    /// List pull requests of the repository
+    List(ListArgs),
+
+    /// Show a pull request
+    View(ViewArgs),
+
+    /// Show the checks of a pull request
+    Checks(ChecksArgs),
+
+    /// Fetch a pull request and switch to its branch
+    Checkout(CheckoutArgs),
+
+    /// Merge a pull request
+    Merge(MergeArgs),
+
+    /// Point the pull requests of a stack at the branch below them after one merged
+    Restack,
+
+    /// Close a pull request
+    Close(TargetArgs),
+
+    /// Reopen a closed pull request
+    Reopen(TargetArgs),
+
+    /// Mark a draft pull request as ready for review
+    Ready(ReadyArgs),
+
+    /// Approve, request changes on or comment on a pull request
+    Review(ReviewArgs),
+
+    /// Comment on a pull request
+    Comment(CommentArgs),
+
+    /// Show the discussion and review threads of a pull request
+    Comments(CommentsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CreateArgs {
+    /// Options of the driver, see `create --help`
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub args: Vec<OsString>,
+}
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    #[clap(flatten)]
+    pub filter: ListFilter,
+
+    /// Print as json
+    #[clap(short, long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ViewArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    /// Open the pull request in the browser
+    #[clap(short, long)]
+    pub web: bool,
+
+    /// Print as json
+    #[clap(short, long)]
+    pub json: bool,
+}
+
+/// Fewest seconds between polls of `checks --watch`, to stay clear of rate limits
+const MIN_INTERVAL: u64 = 5;
+
+#[derive(Args, Debug)]
+pub struct ChecksArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    /// Wait until all checks completed
+    #[clap(short, long)]
+    pub watch: bool,
+
+    /// Seconds between polls while watching (at least 5)
+    #[clap(short, long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(MIN_INTERVAL..))]
+    pub interval: u64,
+
+    /// Seconds to wait for checks to be reported and completed while watching
+    #[clap(short, long, default_value_t = 3600)]
+    pub timeout: u64,
+
+    /// Print as json
+    #[clap(short, long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CheckoutArgs {
+    /// Number or url of the pull request
+    pub number: PullNumber,
+
+    /// Name of the local branch (defaults to the pull request's branch, owner-branch for forks)
+    #[clap(short, long)]
+    pub branch: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct MergeArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    #[clap(flatten)]
+    pub options: MergeOptions,
+
+    #[clap(flatten)]
+    pub refs: Refs,
+}
+
+#[derive(Args, Debug)]
+pub struct TargetArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReadyArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    /// Convert the pull request back to a draft
+    #[clap(short, long)]
+    pub undo: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ReviewArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    #[clap(flatten)]
+    pub verdict: VerdictArgs,
+
+    /// Body of the review (`-` for stdin, `@file`, `@edit` for the editor,
+    /// `@./edit` for a file named edit)
+    #[clap(short, long)]
+    pub body: Option<Desc>,
+
+    /// File of line comments, one `path:line: text` per line
+    #[clap(short = 'f', long)]
+    pub comments: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct VerdictArgs {
+    /// Approve the pull request
+    #[clap(short, long)]
+    pub approve: bool,
+
+    /// Request changes on the pull request
+    #[clap(short, long)]
+    pub request_changes: bool,
+
+    /// Comment on the pull request
+    #[clap(short, long)]
+    pub comment: bool,
+}
+
+impl VerdictArgs {
+    pub fn verdict(&self) -> Verdict {
+        if self.approve {
+            Verdict::Approve
+        } else if self.request_changes {
+            Verdict::RequestChanges
+        } else {
+            Verdict::Comment
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct CommentArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    /// Body of the comment (`-` for stdin, `@file`, `@edit` for the editor,
+    /// `@./edit` for a file named edit)
+    #[clap(short, long, default_value = "@edit")]
+    pub body: Desc,
+}
+
+#[derive(Args, Debug)]
+pub struct CommentsArgs {
+    /// Number or url of the pull request (defaults to the one of the current branch)
+    pub number: Option<PullNumber>,
+
+    /// Print as json
+    #[clap(short, long)]
+    pub json: bool,
+}
+
+impl Cli {
+    pub fn command(self) -> Command {
+        self.command.unwrap_or(Command::Create(self.create))
+    }
+}
+
+impl CreateArgs {
+    /// Arguments for the drivers cli, including a binary name
+    pub fn argv(self) -> Vec<OsString> {
+        let mut argv = vec![OsString::from("tidal-git create")];
+        argv.extend(self.args);
+        argv
+    }
+}
@@ -0,0 +1,15 @@
+use std::env;
+
+/// `TIDAL_TOKEN`, falling back to `GITHUB_TOKEN` and `GH_TOKEN` for github.com
+/// so a github token is never sent to another host
+pub fn token(host: &str) -> Option<String> {
+    let mut vars = vec!["TIDAL_TOKEN"];
+    if host == "api.github.com" {
+        vars.extend(["GITHUB_TOKEN", "GH_TOKEN"]);
+    }
+
+    vars.into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .map(|token| token.trim().to_string())
+        .find(|token| !token.is_empty())
+}
@@ -0,0 +1,73 @@
+use std::{fs, io, path::Path};
+
+/// A plaintext token, refused if others may read it
+pub fn token(path: &Path) -> io::Result<Option<String>> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = metadata.permissions().mode();
+        if mode & 0o077 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "{} is accessible by others (mode {:o}), chmod 600 it",
+                    path.display(),
+                    mode & 0o777
+                ),
+            ));
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = metadata;
+
+    Ok(Some(fs::read_to_string(path)?.trim().to_string()))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
+
+    use super::token;
+
+    fn creds(name: &str, mode: u32) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("tidal-{}-{name}", std::process::id()));
+        fs::write(&path, "secret\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        path
+    }
+
+    #[test]
+    fn private_file() {
+        let path = creds("private", 0o600);
+        let read = token(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.unwrap(), Some("secret".to_string()));
+    }
+
+    #[test]
+    fn readable_by_others() {
+        let path = creds("shared", 0o644);
+        let read = token(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read.unwrap_err().kind(),
+            std::io::ErrorKind::PermissionDenied
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        let path = std::env::temp_dir().join("tidal-missing-creds");
+        assert_eq!(token(&path).unwrap(), None);
+    }
+}
@@ -0,0 +1,61 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use gix::Repository;
+
+/// The password `git credential fill` has for the web host of `host`
+pub fn token(repo: &Repository, host: &str) -> io::Result<Option<String>> {
+    let mut child = Command::new("git")
+        .current_dir(repo.work_dir().unwrap_or(repo.git_dir()))
+        .args(["credential", "fill"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        write!(stdin, "protocol=https\nhost={}\n\n", web_host(host))?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let password = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("password="))
+        .map(|password| password.to_string());
+
+    Ok(password)
+}
+
+/// Credentials are stored for the host the repositories are cloned from,
+/// `api.github.com` and `github.example.com/api/v3` are `github.com` and `github.example.com`
+fn web_host(host: &str) -> &str {
+    let host = host.split('/').next().unwrap_or(host);
+    host.strip_prefix("api.").unwrap_or(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::web_host;
+
+    #[test]
+    fn github_api() {
+        assert_eq!(web_host("api.github.com"), "github.com");
+    }
+
+    #[test]
+    fn enterprise_api_path() {
+        assert_eq!(web_host("github.example.com/api/v3"), "github.example.com");
+    }
+
+    #[test]
+    fn plain_host() {
+        assert_eq!(web_host("gitlab.com"), "gitlab.com");
+    }
+}
@@ -1,15 +1,190 @@
-use std::io;
+use std::{fmt, io, path::PathBuf, str::FromStr};
 
+use gix::Repository;
+
+mod env;
+mod file;
+mod git;
 mod gpg;
+mod pass;
+
+/// A source of tokens, tried in order until one has a token for the host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// `TIDAL_TOKEN`, and `GITHUB_TOKEN` or `GH_TOKEN` for github
+    Env,
+    /// `~/.config/creds/{host}`, readable only by the user
+    File,
+    /// `git credential fill`
+    Git,
+    /// `pass show tidal/{host}`
+    Pass,
+    /// `~/.config/creds/{host}.gpg`
+    Gpg,
+}
+
+#[derive(Debug)]
+pub enum CredError {
+    /// A name in `tidal.<host>.credential` that isn't a provider
+    UnknownProvider(String),
+    /// No provider had a token for the host, with the errors of those that failed
+    NoToken(String, Vec<(Provider, io::Error)>),
+}
+
+impl fmt::Display for CredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredError::UnknownProvider(name) => write!(f, "unknown credential provider {name}")?,
+            CredError::NoToken(host, failed) => {
+                write!(f, "no token found for {host}")?;
+                for (provider, e) in failed {
+                    write!(f, "\n  {provider}: {e}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for CredError {}
+
+const DEFAULT_CHAIN: [Provider; 5] = [
+    Provider::Env,
+    Provider::File,
+    Provider::Git,
+    Provider::Pass,
+    Provider::Gpg,
+];
 
-pub fn find_token(host: &str) -> io::Result<String> {
+impl FromStr for Provider {
+    type Err = CredError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "env" => Ok(Provider::Env),
+            "file" => Ok(Provider::File),
+            "git" => Ok(Provider::Git),
+            "pass" => Ok(Provider::Pass),
+            "gpg" => Ok(Provider::Gpg),
+            _ => Err(CredError::UnknownProvider(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Provider::Env => write!(f, "env")?,
+            Provider::File => write!(f, "file")?,
+            Provider::Git => write!(f, "git")?,
+            Provider::Pass => write!(f, "pass")?,
+            Provider::Gpg => write!(f, "gpg")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Provider {
+    /// The token for `host`, `None` if the provider has none
+    fn token(&self, repo: &Repository, host: &str) -> io::Result<Option<String>> {
+        match self {
+            Provider::Env => Ok(env::token(host)),
+            Provider::File => file::token(&creds_path(host, "")?),
+            Provider::Git => git::token(repo, host),
+            Provider::Pass => pass::token(host),
+            Provider::Gpg => {
+                let path = creds_path(host, ".gpg")?;
+                if !path.exists() {
+                    return Ok(None);
+                }
+
+                let token = gpg::token(&path)?;
+                let token = String::from_utf8(token)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                Ok(Some(token.trim().to_string()))
+            }
+        }
+    }
+}
+
+/// Find the token for `host` with the providers of `tidal.<host>.credential`,
+/// or all of them in the order of [`Provider`]
+pub fn find_token(repo: &Repository, host: &str) -> Result<String, CredError> {
+    let snapshot = repo.config_snapshot();
+    let configured = snapshot
+        .strings("tidal", Some(host.into()), "credential")
+        .unwrap_or_default();
+    let configured: Vec<_> = configured.iter().map(|value| value.to_string()).collect();
+
+    let mut failed = Vec::new();
+    for provider in chain(&configured)? {
+        match provider.token(repo, host) {
+            Ok(Some(token)) if !token.is_empty() => return Ok(token),
+            Ok(_) => (),
+            Err(e) => failed.push((provider, e)),
+        }
+    }
+
+    Err(CredError::NoToken(host.to_string(), failed))
+}
+
+/// Providers of the configured values, repeated or comma separated
+fn chain(configured: &[String]) -> Result<Vec<Provider>, CredError> {
+    if configured.is_empty() {
+        return Ok(DEFAULT_CHAIN.to_vec());
+    }
+
+    let mut chain = Vec::new();
+    for value in configured {
+        for name in value.split(',') {
+            let name = name.trim();
+            if !name.is_empty() {
+                chain.push(name.parse()?);
+            }
+        }
+    }
+
+    Ok(chain)
+}
+
+fn creds_path(host: &str, ext: &str) -> io::Result<PathBuf> {
     let mut cfg = dirs::config_dir().ok_or(io::Error::new(io::ErrorKind::NotFound, ""))?;
     cfg.push("creds");
-    cfg.push(format!("{host}.gpg"));
+    cfg.push(format!("{host}{ext}"));
+
+    Ok(cfg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chain, CredError, Provider, DEFAULT_CHAIN};
 
-    if let Ok(token) = gpg::token(&cfg) {
-        return Ok(String::from_utf8(token).unwrap());
+    fn parse(values: &[&str]) -> Result<Vec<Provider>, CredError> {
+        let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        chain(&values)
     }
 
-    todo!()
+    #[test]
+    fn default_chain() {
+        assert_eq!(parse(&[]).unwrap(), DEFAULT_CHAIN.to_vec());
+    }
+
+    #[test]
+    fn repeated_and_comma_separated() {
+        assert_eq!(
+            parse(&["pass, env", "git", " ,gpg"]).unwrap(),
+            vec![Provider::Pass, Provider::Env, Provider::Git, Provider::Gpg]
+        );
+    }
+
+    #[test]
+    fn unknown_provider() {
+        match parse(&["env,keyring"]) {
+            Err(CredError::UnknownProvider(name)) => assert_eq!(name, "keyring"),
+            other => panic!("expected an unknown provider, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,25 @@
+use std::{io, process::Command};
+
+/// The first line of `pass show tidal/{host}`
+pub fn token(host: &str) -> io::Result<Option<String>> {
+    let output = match Command::new("pass")
+        .arg("show")
+        .arg(format!("tidal/{host}"))
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let token = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string());
+
+    Ok(token)
+}
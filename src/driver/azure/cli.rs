@@ -0,0 +1,41 @@
+use crate::tidal::{Common, Desc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version)]
+pub struct AzureCli {
+    /// Title of the pull request
+    #[clap(short, long)]
+    pub title: String,
+
+    /// Description of pull request
+    #[clap(short, long)]
+    pub description: Option<Desc>,
+
+    /// Open page for pull request
+    #[clap(short, long)]
+    pub open: bool,
+
+    /// Draft pull request
+    #[clap(short = 'D', long)]
+    pub draft: bool,
+
+    /// Work item to link to the pull request (repeatable)
+    #[clap(short, long = "work-item")]
+    pub work_items: Vec<u64>,
+
+    /// Complete the pull request once all policies pass
+    #[clap(short, long)]
+    pub auto_complete: bool,
+
+    /// Squash commits when completed
+    #[clap(short, long, requires = "auto_complete")]
+    pub squash: bool,
+
+    /// Delete source branch when completed
+    #[clap(short = 'x', long, requires = "auto_complete")]
+    pub delete_source_branch: bool,
+
+    #[clap(flatten)]
+    pub common: Common,
+}
@@ -0,0 +1,57 @@
+use crate::{cred::CredError, errors::Error};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum AzureError {
+    FailedDescription,
+    Http(reqwest::Error),
+    MalformedRemote(String),
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    NoToken(CredError),
+    FailedToOpen,
+}
+
+impl From<reqwest::Error> for AzureError {
+    fn from(value: reqwest::Error) -> Self {
+        if let Some(status) = value.status() {
+            match status.into() {
+                401 => return AzureError::Unauthorized,
+                403 => return AzureError::Forbidden,
+                404 => return AzureError::NotFound,
+                409 => return AzureError::Conflict,
+                _ => (),
+            }
+        }
+
+        AzureError::Http(value)
+    }
+}
+
+impl std::error::Error for AzureError {}
+
+impl Display for AzureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AzureError::Unauthorized => write!(f, "personal access token was rejected")?,
+            AzureError::Forbidden => write!(f, "not permitted to create pull request")?,
+            AzureError::NotFound => write!(f, "repository not found")?,
+            AzureError::Conflict => write!(f, "pull request already exists")?,
+            AzureError::MalformedRemote(url) => write!(f, "not an azure repos url: {url}")?,
+            AzureError::FailedDescription => write!(f, "failed to read description")?,
+            AzureError::NoToken(e) => write!(f, "{e}")?,
+            AzureError::Http(e) => write!(f, "http error: {e}")?,
+            AzureError::FailedToOpen => write!(f, "failed to open pull request in browser")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for AzureError {
+    fn status(&self) -> i32 {
+        8
+    }
+}
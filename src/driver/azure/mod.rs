@@ -0,0 +1,219 @@
+use std::ffi::OsString;
+
+use super::{DriverError, Runner};
+use crate::cred::find_token;
+use crate::driver::azure::cli::AzureCli;
+use crate::driver::azure::req::{
+    CompletionOptions, CreatePullRequest, MergeStrategy, SetAutoComplete, WorkItemRef,
+};
+use crate::errors::SysErrorHandler;
+use crate::tidal::Req;
+use clap::Parser;
+
+pub use err::AzureError;
+use gix::remote::Direction;
+use gix::Repository;
+
+mod cli;
+mod err;
+mod req;
+
+/// Create a pull request with azure devops [pull request api](https://learn.microsoft.com/en-us/rest/api/azure/devops/git/pull-requests/create)
+pub struct Azure {
+    /// Base use
+    pub base: String,
+}
+
+/// Location of a repository in azure devops
+pub struct AzureRepo {
+    pub org: String,
+    pub project: String,
+    pub name: String,
+}
+
+impl Azure {
+    async fn create_pull_request(
+        &self,
+        repo: Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), AzureError> {
+        let cli = AzureCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+        req.push(&repo, &cli.common).handle_system_error();
+
+        let description = get_description(&cli)?;
+        let target = repo_parts(&repo, &req.to.remote)?;
+
+        let work_item_refs = cli
+            .work_items
+            .iter()
+            .map(|id| WorkItemRef { id: id.to_string() })
+            .collect();
+
+        let payload = CreatePullRequest {
+            title: cli.title.clone(),
+            description,
+            source_ref_name: format!("refs/heads/{}", req.from.branch),
+            target_ref_name: format!("refs/heads/{}", req.to.branch),
+            is_draft: cli.draft,
+            work_item_refs,
+        };
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(AzureError::NoToken)?;
+        let res = req::send_request(&client, &self.base, &target, &token, payload).await?;
+
+        if cli.auto_complete {
+            let merge_strategy = if cli.squash {
+                MergeStrategy::Squash
+            } else {
+                MergeStrategy::NoFastForward
+            };
+
+            let auto_complete = SetAutoComplete {
+                auto_complete_set_by: res.created_by,
+                completion_options: CompletionOptions {
+                    delete_source_branch: cli.delete_source_branch,
+                    merge_strategy,
+                },
+            };
+
+            req::set_auto_complete(
+                &client,
+                &self.base,
+                &target,
+                &token,
+                res.pull_request_id,
+                auto_complete,
+            )
+            .await?;
+        }
+
+        if cli.open {
+            let url = format!(
+                "{}/pullrequest/{}",
+                res.repository.web_url, res.pull_request_id
+            );
+            open::that(url).map_err(|_| AzureError::FailedToOpen)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn get_description(cli: &AzureCli) -> Result<String, AzureError> {
+    let body = if let Some(desc) = &cli.description {
+        match desc.read() {
+            Ok(s) => s,
+            Err(_) => return Err(AzureError::FailedDescription),
+        }
+    } else {
+        "".to_string()
+    };
+    Ok(body)
+}
+
+fn repo_parts(repo: &Repository, remote: &str) -> Result<AzureRepo, AzureError> {
+    let remote = repo.find_remote(remote).unwrap();
+    let url = remote.url(Direction::Push).unwrap();
+
+    let host = url.host().unwrap_or_default();
+    let path = url.path.to_string();
+
+    parse_remote(host, &path)
+        .ok_or_else(|| AzureError::MalformedRemote(url.to_bstring().to_string()))
+}
+
+/// Split an azure repos remote into its organization, project and repository
+///
+/// Understands the following remote shapes:
+/// - `https://dev.azure.com/{org}/{project}/_git/{repo}`
+/// - `git@ssh.dev.azure.com:v3/{org}/{project}/{repo}`
+/// - `https://{org}.visualstudio.com/{project}/_git/{repo}`
+fn parse_remote(host: &str, path: &str) -> Option<AzureRepo> {
+    let path = path.trim_start_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').collect();
+
+    let (org, project, name) = match segments.as_slice() {
+        ["v3", org, project, name] => (*org, *project, *name),
+        [org, project, "_git", name] if host == "dev.azure.com" => (*org, *project, *name),
+        [project, "_git", name] | ["DefaultCollection", project, "_git", name] => {
+            let org = host.strip_suffix(".visualstudio.com")?;
+            (org, *project, *name)
+        }
+        _ => return None,
+    };
+
+    Some(AzureRepo {
+        org: org.to_string(),
+        project: project.to_string(),
+        name: name.to_string(),
+    })
+}
+
+#[async_trait::async_trait]
+impl Runner for Azure {
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.create_pull_request(repo, req, args).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_remote;
+
+    fn parts(host: &str, path: &str) -> Option<(String, String, String)> {
+        parse_remote(host, path).map(|r| (r.org, r.project, r.name))
+    }
+
+    fn expected(org: &str, project: &str, name: &str) -> Option<(String, String, String)> {
+        Some((org.to_string(), project.to_string(), name.to_string()))
+    }
+
+    #[test]
+    fn https_dev_azure() {
+        assert_eq!(
+            parts("dev.azure.com", "/contoso/web/_git/site"),
+            expected("contoso", "web", "site")
+        );
+    }
+
+    #[test]
+    fn ssh_dev_azure() {
+        assert_eq!(
+            parts("ssh.dev.azure.com", "v3/contoso/web/site"),
+            expected("contoso", "web", "site")
+        );
+    }
+
+    #[test]
+    fn visualstudio() {
+        assert_eq!(
+            parts("contoso.visualstudio.com", "/web/_git/site"),
+            expected("contoso", "web", "site")
+        );
+        assert_eq!(
+            parts(
+                "contoso.visualstudio.com",
+                "/DefaultCollection/web/_git/site.git"
+            ),
+            expected("contoso", "web", "site")
+        );
+    }
+
+    #[test]
+    fn not_azure() {
+        assert_eq!(parts("github.com", "/owner/repo.git"), None);
+        assert_eq!(parts("example.com", "/web/_git/site"), None);
+    }
+}
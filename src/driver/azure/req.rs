@@ -0,0 +1,160 @@
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::{AzureError, AzureRepo};
+
+const API_VERSION: &str = "7.1";
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePullRequest {
+    pub title: String,
+    pub description: String,
+    pub source_ref_name: String,
+    pub target_ref_name: String,
+    pub is_draft: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub work_item_refs: Vec<WorkItemRef>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WorkItemRef {
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePullRequestResponse {
+    pub pull_request_id: u64,
+    pub created_by: Identity,
+    pub repository: Repository,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Identity {
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repository {
+    pub web_url: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAutoComplete {
+    pub auto_complete_set_by: Identity,
+    pub completion_options: CompletionOptions,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionOptions {
+    pub delete_source_branch: bool,
+    pub merge_strategy: MergeStrategy,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeStrategy {
+    NoFastForward,
+    Squash,
+}
+
+/// Root of the organization, legacy `{org}.visualstudio.com` hosts already name it
+fn organization_url(base: &str, repo: &AzureRepo) -> String {
+    if base.ends_with(".visualstudio.com") {
+        format!("https://{base}")
+    } else {
+        format!("https://{base}/{}", repo.org)
+    }
+}
+
+fn pull_requests_url(base: &str, repo: &AzureRepo) -> String {
+    format!(
+        "{}/{}/_apis/git/repositories/{}/pullrequests",
+        organization_url(base, repo),
+        repo.project,
+        repo.name
+    )
+}
+
+fn authorize(req: RequestBuilder, token: &str) -> RequestBuilder {
+    req.header("User-Agent", "Tidal")
+        .header("Accept", "application/json")
+        .query(&[("api-version", API_VERSION)])
+        .basic_auth("", Some(token))
+}
+
+pub async fn send_request(
+    client: &Client,
+    base: &str,
+    repo: &AzureRepo,
+    token: &str,
+    req: CreatePullRequest,
+) -> Result<CreatePullRequestResponse, AzureError> {
+    let url = pull_requests_url(base, repo);
+
+    let req = authorize(client.post(url), token).json(&req);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: CreatePullRequestResponse = res.json().await?;
+
+    Ok(res)
+}
+
+pub async fn set_auto_complete(
+    client: &Client,
+    base: &str,
+    repo: &AzureRepo,
+    token: &str,
+    id: u64,
+    req: SetAutoComplete,
+) -> Result<(), AzureError> {
+    let url = format!("{}/{id}", pull_requests_url(base, repo));
+
+    let req = authorize(client.patch(url), token).json(&req);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> AzureRepo {
+        AzureRepo {
+            org: "contoso".to_string(),
+            project: "web".to_string(),
+            name: "site".to_string(),
+        }
+    }
+
+    #[test]
+    fn pull_requests_url_dev_azure() {
+        assert_eq!(
+            pull_requests_url("dev.azure.com", &repo()),
+            "https://dev.azure.com/contoso/web/_apis/git/repositories/site/pullrequests"
+        );
+    }
+
+    #[test]
+    fn pull_requests_url_visualstudio() {
+        assert_eq!(
+            pull_requests_url("contoso.visualstudio.com", &repo()),
+            "https://contoso.visualstudio.com/web/_apis/git/repositories/site/pullrequests"
+        );
+    }
+}
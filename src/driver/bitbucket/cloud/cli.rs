@@ -0,0 +1,29 @@
+use crate::tidal::{Common, Desc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version)]
+pub struct BitbucketCloudCli {
+    /// Title of the pull request
+    #[clap(short, long)]
+    pub title: String,
+
+    /// Description of pull request
+    #[clap(short, long)]
+    pub description: Option<Desc>,
+
+    /// Open page for pull request
+    #[clap(short, long)]
+    pub open: bool,
+
+    /// Draft pull request
+    #[clap(short = 'D', long)]
+    pub draft: bool,
+
+    /// Close source branch when merged
+    #[clap(short = 'x', long)]
+    pub close_source_branch: bool,
+
+    #[clap(flatten)]
+    pub common: Common,
+}
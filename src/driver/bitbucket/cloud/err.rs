@@ -0,0 +1,57 @@
+use crate::{cred::CredError, errors::Error};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum BitbucketCloudError {
+    FailedDescription,
+    Http(reqwest::Error),
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Validation,
+    NoToken(CredError),
+    FailedToOpen,
+}
+
+impl From<reqwest::Error> for BitbucketCloudError {
+    fn from(value: reqwest::Error) -> Self {
+        if let Some(status) = value.status() {
+            match status.into() {
+                400 => return BitbucketCloudError::Validation,
+                401 => return BitbucketCloudError::Unauthorized,
+                403 => return BitbucketCloudError::Forbidden,
+                404 => return BitbucketCloudError::NotFound,
+                _ => (),
+            }
+        }
+
+        BitbucketCloudError::Http(value)
+    }
+}
+
+impl std::error::Error for BitbucketCloudError {}
+
+impl Display for BitbucketCloudError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitbucketCloudError::Unauthorized => write!(f, "token was rejected")?,
+            BitbucketCloudError::Forbidden => write!(f, "not permitted to create pull request")?,
+            BitbucketCloudError::NotFound => write!(f, "repository not found")?,
+            BitbucketCloudError::Validation => write!(f, "pull request failed validation")?,
+            BitbucketCloudError::FailedDescription => write!(f, "failed to read description")?,
+            BitbucketCloudError::NoToken(e) => write!(f, "{e}")?,
+            BitbucketCloudError::Http(e) => write!(f, "http error: {e}")?,
+            BitbucketCloudError::FailedToOpen => {
+                write!(f, "failed to open pull request in browser")?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for BitbucketCloudError {
+    fn status(&self) -> i32 {
+        6
+    }
+}
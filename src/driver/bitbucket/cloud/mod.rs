@@ -0,0 +1,105 @@
+use std::ffi::OsString;
+
+use super::repo_parts;
+use crate::cred::find_token;
+use crate::driver::bitbucket::cloud::cli::BitbucketCloudCli;
+use crate::driver::bitbucket::cloud::req::{Branch, CreatePullRequest, Endpoint, Repository};
+use crate::driver::{DriverError, Runner};
+use crate::errors::SysErrorHandler;
+use crate::tidal::Req;
+use clap::Parser;
+
+pub use err::BitbucketCloudError;
+
+mod cli;
+mod err;
+mod req;
+
+/// Create a pull request with bitbucket clouds [pull request api](https://developer.atlassian.com/cloud/bitbucket/rest/api-group-pullrequests/#api-repositories-workspace-repo-slug-pullrequests-post)
+pub struct BitbucketCloud {
+    /// Base use
+    pub base: String,
+}
+
+impl BitbucketCloud {
+    async fn create_pull_request(
+        &self,
+        repo: gix::Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), BitbucketCloudError> {
+        let cli = BitbucketCloudCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+        req.push(&repo, &cli.common).handle_system_error();
+
+        let description = get_description(&cli)?;
+
+        let (our, our_slug) = repo_parts(&repo, &req.from.remote);
+        let (workspace, slug) = repo_parts(&repo, &req.to.remote);
+
+        let repository = if (&our, &our_slug) != (&workspace, &slug) {
+            Some(Repository {
+                full_name: format!("{our}/{our_slug}"),
+            })
+        } else {
+            None
+        };
+
+        let payload = CreatePullRequest {
+            title: cli.title,
+            description,
+            source: Endpoint {
+                branch: Branch {
+                    name: req.from.branch,
+                },
+                repository,
+            },
+            destination: Endpoint {
+                branch: Branch {
+                    name: req.to.branch,
+                },
+                repository: None,
+            },
+            close_source_branch: cli.close_source_branch,
+            draft: cli.draft,
+        };
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(BitbucketCloudError::NoToken)?;
+        let res =
+            req::send_request(&client, &self.base, &workspace, &slug, &token, payload).await?;
+
+        if cli.open {
+            open::that(res.links.html.href).map_err(|_| BitbucketCloudError::FailedToOpen)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn get_description(cli: &BitbucketCloudCli) -> Result<String, BitbucketCloudError> {
+    let body = if let Some(desc) = &cli.description {
+        match desc.read() {
+            Ok(s) => s,
+            Err(_) => return Err(BitbucketCloudError::FailedDescription),
+        }
+    } else {
+        "".to_string()
+    };
+    Ok(body)
+}
+
+#[async_trait::async_trait]
+impl Runner for BitbucketCloud {
+    async fn create(
+        &self,
+        repo: gix::Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.create_pull_request(repo, req, args).await?;
+
+        Ok(())
+    }
+}
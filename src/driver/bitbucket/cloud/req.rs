@@ -0,0 +1,74 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::BitbucketCloudError;
+
+#[derive(Serialize, Debug)]
+pub struct CreatePullRequest {
+    pub title: String,
+    pub description: String,
+    pub source: Endpoint,
+    pub destination: Endpoint,
+    pub close_source_branch: bool,
+    pub draft: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Endpoint {
+    pub branch: Branch,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<Repository>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Branch {
+    pub name: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Repository {
+    pub full_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreatePullRequestResponse {
+    pub links: Links,
+}
+
+#[derive(Deserialize)]
+pub struct Links {
+    pub html: Link,
+}
+
+#[derive(Deserialize)]
+pub struct Link {
+    pub href: String,
+}
+
+pub async fn send_request(
+    client: &Client,
+    base: &str,
+    workspace: &str,
+    slug: &str,
+    token: &str,
+    req: CreatePullRequest,
+) -> Result<CreatePullRequestResponse, BitbucketCloudError> {
+    let url = format!("https://{base}/2.0/repositories/{workspace}/{slug}/pullrequests");
+
+    let req = client
+        .post(url)
+        .header("User-Agent", "Tidal")
+        .header("Accept", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&req);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: CreatePullRequestResponse = res.json().await?;
+
+    Ok(res)
+}
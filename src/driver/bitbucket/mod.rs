@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use gix::remote::Direction;
+use gix::Repository;
+
+pub use cloud::{BitbucketCloud, BitbucketCloudError};
+pub use server::{BitbucketServer, BitbucketServerError};
+
+mod cloud;
+mod server;
+
+/// Last two segments of a remotes path,
+/// (workspace, slug) for bitbucket cloud and (project key, slug) for bitbucket server
+fn repo_parts(repo: &Repository, remote: &str) -> (String, String) {
+    let remote = repo.find_remote(remote).unwrap();
+    let url = remote.url(Direction::Push).unwrap();
+
+    let mut path = PathBuf::from(url.path.to_string());
+
+    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    let repo = name.strip_suffix(".git").unwrap_or(&name).to_string();
+    path.pop();
+    let owner = path.file_name().unwrap().to_string_lossy().to_string();
+
+    (owner, repo)
+}
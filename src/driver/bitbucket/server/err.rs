@@ -0,0 +1,60 @@
+use crate::{cred::CredError, errors::Error};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum BitbucketServerError {
+    FailedDescription,
+    Http(reqwest::Error),
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    Validation,
+    NoToken(CredError),
+    FailedToOpen,
+}
+
+impl From<reqwest::Error> for BitbucketServerError {
+    fn from(value: reqwest::Error) -> Self {
+        if let Some(status) = value.status() {
+            match status.into() {
+                400 => return BitbucketServerError::Validation,
+                401 => return BitbucketServerError::Unauthorized,
+                403 => return BitbucketServerError::Forbidden,
+                404 => return BitbucketServerError::NotFound,
+                409 => return BitbucketServerError::Conflict,
+                _ => (),
+            }
+        }
+
+        BitbucketServerError::Http(value)
+    }
+}
+
+impl std::error::Error for BitbucketServerError {}
+
+impl Display for BitbucketServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitbucketServerError::Unauthorized => write!(f, "token was rejected")?,
+            BitbucketServerError::Forbidden => write!(f, "not permitted to create pull request")?,
+            BitbucketServerError::NotFound => write!(f, "repository not found")?,
+            BitbucketServerError::Conflict => write!(f, "pull request already exists")?,
+            BitbucketServerError::Validation => write!(f, "pull request failed validation")?,
+            BitbucketServerError::FailedDescription => write!(f, "failed to read description")?,
+            BitbucketServerError::NoToken(e) => write!(f, "{e}")?,
+            BitbucketServerError::Http(e) => write!(f, "http error: {e}")?,
+            BitbucketServerError::FailedToOpen => {
+                write!(f, "failed to open pull request in browser")?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for BitbucketServerError {
+    fn status(&self) -> i32 {
+        7
+    }
+}
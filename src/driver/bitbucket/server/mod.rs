@@ -0,0 +1,110 @@
+use std::ffi::OsString;
+
+use super::repo_parts;
+use crate::cred::find_token;
+use crate::driver::bitbucket::server::cli::BitbucketServerCli;
+use crate::driver::bitbucket::server::req::{
+    CreatePullRequest, Project, Ref, Repository, Reviewer, User,
+};
+use crate::driver::{DriverError, Runner};
+use crate::errors::SysErrorHandler;
+use crate::tidal::Req;
+use clap::Parser;
+
+pub use err::BitbucketServerError;
+
+mod cli;
+mod err;
+mod req;
+
+/// Create a pull request with bitbucket server / data centers [pull request api](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-pull-requests/#api-api-latest-projects-projectkey-repos-repositoryslug-pull-requests-post)
+pub struct BitbucketServer {
+    /// Base use
+    pub base: String,
+}
+
+impl BitbucketServer {
+    async fn create_pull_request(
+        &self,
+        repo: gix::Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), BitbucketServerError> {
+        let cli = BitbucketServerCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+        req.push(&repo, &cli.common).handle_system_error();
+
+        let description = get_description(&cli)?;
+
+        let (from_key, from_slug) = repo_parts(&repo, &req.from.remote);
+        let (key, slug) = repo_parts(&repo, &req.to.remote);
+
+        let reviewers = cli
+            .reviewers
+            .into_iter()
+            .map(|name| Reviewer {
+                user: User { name },
+            })
+            .collect();
+
+        let payload = CreatePullRequest {
+            title: cli.title,
+            description,
+            from_ref: Ref {
+                id: format!("refs/heads/{}", req.from.branch),
+                repository: Repository {
+                    slug: from_slug,
+                    project: Project { key: from_key },
+                },
+            },
+            to_ref: Ref {
+                id: format!("refs/heads/{}", req.to.branch),
+                repository: Repository {
+                    slug: slug.clone(),
+                    project: Project { key: key.clone() },
+                },
+            },
+            draft: cli.draft,
+            reviewers,
+        };
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(BitbucketServerError::NoToken)?;
+        let res = req::send_request(&client, &self.base, &key, &slug, &token, payload).await?;
+
+        if cli.open {
+            if let Some(link) = res.links.this.into_iter().next() {
+                open::that(link.href).map_err(|_| BitbucketServerError::FailedToOpen)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn get_description(cli: &BitbucketServerCli) -> Result<String, BitbucketServerError> {
+    let body = if let Some(desc) = &cli.description {
+        match desc.read() {
+            Ok(s) => s,
+            Err(_) => return Err(BitbucketServerError::FailedDescription),
+        }
+    } else {
+        "".to_string()
+    };
+    Ok(body)
+}
+
+#[async_trait::async_trait]
+impl Runner for BitbucketServer {
+    async fn create(
+        &self,
+        repo: gix::Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.create_pull_request(repo, req, args).await?;
+
+        Ok(())
+    }
+}
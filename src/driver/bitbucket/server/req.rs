@@ -0,0 +1,86 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::BitbucketServerError;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePullRequest {
+    pub title: String,
+    pub description: String,
+    pub from_ref: Ref,
+    pub to_ref: Ref,
+    pub draft: bool,
+    pub reviewers: Vec<Reviewer>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Ref {
+    pub id: String,
+    pub repository: Repository,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Repository {
+    pub slug: String,
+    pub project: Project,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Project {
+    pub key: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Reviewer {
+    pub user: User,
+}
+
+#[derive(Serialize, Debug)]
+pub struct User {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreatePullRequestResponse {
+    pub links: Links,
+}
+
+#[derive(Deserialize)]
+pub struct Links {
+    #[serde(rename = "self")]
+    pub this: Vec<Link>,
+}
+
+#[derive(Deserialize)]
+pub struct Link {
+    pub href: String,
+}
+
+pub async fn send_request(
+    client: &Client,
+    base: &str,
+    key: &str,
+    slug: &str,
+    token: &str,
+    req: CreatePullRequest,
+) -> Result<CreatePullRequestResponse, BitbucketServerError> {
+    let url = format!("https://{base}/rest/api/1.0/projects/{key}/repos/{slug}/pull-requests");
+
+    let req = client
+        .post(url)
+        .header("User-Agent", "Tidal")
+        .header("Accept", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&req);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: CreatePullRequestResponse = res.json().await?;
+
+    Ok(res)
+}
@@ -1,7 +1,16 @@
 use gix::{bstr::BString, Repository};
 use std::collections::HashSet;
 
-use super::{github::Github, Driver};
+use super::{
+    azure::Azure,
+    bitbucket::{BitbucketCloud, BitbucketServer},
+    email::Email,
+    gerrit::Gerrit,
+    gitea::Gitea,
+    github::Github,
+    gitlab::Gitlab,
+    Driver,
+};
 
 pub fn find_driver(repo: &Repository) -> Result<Driver, DriverError> {
     let host = discover_host(repo)?;
@@ -40,6 +49,32 @@ fn build_driver(
             base: host.to_string(),
         })),
 
+        b"gitlab" => Ok(Driver::Gitlab(Gitlab {
+            base: host.to_string(),
+        })),
+
+        b"gitea" | b"forgejo" => Ok(Driver::Gitea(Gitea {
+            base: host.to_string(),
+        })),
+
+        b"bitbucket" | b"bitbucket-cloud" => Ok(Driver::BitbucketCloud(BitbucketCloud {
+            base: host.to_string(),
+        })),
+
+        b"bitbucket-server" | b"bitbucket-datacenter" => {
+            Ok(Driver::BitbucketServer(BitbucketServer {
+                base: host.to_string(),
+            }))
+        }
+
+        b"azure" => Ok(Driver::Azure(Azure {
+            base: host.to_string(),
+        })),
+
+        b"email" => Ok(Driver::Email(Email)),
+
+        b"gerrit" => Ok(Driver::Gerrit(Gerrit)),
+
         _ => Err(DriverError::UnknownDriver(ty.into())),
     }
 }
@@ -50,6 +85,24 @@ fn build_default_deriver(host: &str) -> Option<Driver> {
             base: "api.github.com".to_string(),
         })),
 
+        "gitlab.com" => Some(Driver::Gitlab(Gitlab {
+            base: "gitlab.com".to_string(),
+        })),
+
+        "codeberg.org" => Some(Driver::Gitea(Gitea {
+            base: "codeberg.org".to_string(),
+        })),
+
+        "bitbucket.org" => Some(Driver::BitbucketCloud(BitbucketCloud {
+            base: "api.bitbucket.org".to_string(),
+        })),
+
+        "dev.azure.com" | "ssh.dev.azure.com" => Some(Driver::Azure(Azure {
+            base: "dev.azure.com".to_string(),
+        })),
+
+        "git.sr.ht" => Some(Driver::Email(Email)),
+
         _ => None,
     }
 }
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::tidal::{Common, Desc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version)]
+pub struct EmailCli {
+    /// Subject of the cover letter
+    #[clap(short, long)]
+    pub title: Option<String>,
+
+    /// Body of the cover letter
+    #[clap(short, long)]
+    pub description: Option<Desc>,
+
+    /// Recipient of the series (repeatable, defaults to sendemail.to)
+    #[clap(long)]
+    pub to: Vec<String>,
+
+    /// Carbon copy recipient of the series (repeatable, defaults to sendemail.cc)
+    #[clap(long)]
+    pub cc: Vec<String>,
+
+    /// Mark the series as a reroll (v2, v3, ...)
+    #[clap(short = 'v', long)]
+    pub reroll_count: Option<u32>,
+
+    /// Subject prefix instead of PATCH
+    #[clap(short = 'p', long)]
+    pub subject_prefix: Option<String>,
+
+    /// Mbox file to write the series to (defaults to <from-branch>.mbox)
+    #[clap(short = 'O', long)]
+    pub output: Option<PathBuf>,
+
+    /// Send the series instead of writing a mbox file
+    #[clap(short, long)]
+    pub send: bool,
+
+    /// Command to send each mail with (defaults to sendemail.sendmailcmd)
+    #[clap(long)]
+    pub sendmail: Option<String>,
+
+    #[clap(flatten)]
+    pub common: Common,
+}
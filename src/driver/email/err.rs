@@ -0,0 +1,39 @@
+use crate::errors::Error;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum EmailError {
+    FailedDescription,
+    Git(std::io::Error),
+    FormatPatch(String),
+    NoPatches,
+    NoRecipient,
+    Output(std::io::Error),
+    Sendmail(std::io::Error),
+    SendmailFailed(std::process::ExitStatus),
+}
+
+impl std::error::Error for EmailError {}
+
+impl Display for EmailError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmailError::FailedDescription => write!(f, "failed to read description")?,
+            EmailError::Git(e) => write!(f, "failed to run git: {e}")?,
+            EmailError::FormatPatch(e) => write!(f, "git format-patch failed: {e}")?,
+            EmailError::NoPatches => write!(f, "no commits to send")?,
+            EmailError::NoRecipient => write!(f, "no recipient (set --to or sendemail.to)")?,
+            EmailError::Output(e) => write!(f, "failed to write mbox: {e}")?,
+            EmailError::Sendmail(e) => write!(f, "failed to run sendmail: {e}")?,
+            EmailError::SendmailFailed(status) => write!(f, "sendmail failed: {status}")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for EmailError {
+    fn status(&self) -> i32 {
+        9
+    }
+}
@@ -0,0 +1,150 @@
+use std::ffi::OsString;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::{DriverError, Runner};
+use crate::driver::email::cli::EmailCli;
+use crate::driver::email::patch::Series;
+use crate::errors::SysErrorHandler;
+use crate::tidal::Req;
+use clap::Parser;
+
+pub use err::EmailError;
+use gix::Repository;
+
+mod cli;
+mod err;
+mod patch;
+
+/// Send a branch for review as a [git format-patch](https://git-scm.com/docs/git-format-patch) series,
+/// either written to a mbox file or handed to a sendmail command
+pub struct Email;
+
+impl Email {
+    async fn send_series(
+        &self,
+        repo: Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), EmailError> {
+        let cli = EmailCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+        req.push(&repo, &cli.common).handle_system_error();
+
+        let snapshot = repo.config_snapshot();
+        let to = config_default(&cli.to, &snapshot, "to");
+        let cc = config_default(&cli.cc, &snapshot, "cc");
+
+        let blurb = get_blurb(&cli)?;
+        let subject = cli.title.clone().unwrap_or_else(|| req.from.branch.clone());
+        let cover = if cli.title.is_some() || blurb.is_some() {
+            Some((subject.as_str(), blurb.as_deref().unwrap_or_default()))
+        } else {
+            None
+        };
+
+        let series = Series {
+            range: format!("{}..{}", req.to.branch, req.from.branch),
+            cover,
+            to: &to,
+            cc: &cc,
+            reroll_count: cli.reroll_count,
+            subject_prefix: cli.subject_prefix.as_deref(),
+        };
+
+        let dir = repo.work_dir().unwrap_or(repo.git_dir());
+        let mbox = patch::format_patch(dir, &series)?;
+
+        if cli.send {
+            if to.is_empty() {
+                return Err(EmailError::NoRecipient);
+            }
+
+            let sendmail = cli
+                .sendmail
+                .clone()
+                .or_else(|| {
+                    snapshot
+                        .string("sendemail.sendmailcmd")
+                        .map(|s| s.to_string())
+                })
+                .unwrap_or_else(|| "sendmail -t -i".to_string());
+
+            for message in patch::messages(&mbox) {
+                sendmail_message(&sendmail, &message)?;
+            }
+        } else {
+            let output = cli
+                .output
+                .clone()
+                .unwrap_or_else(|| format!("{}.mbox", req.from.branch.replace('/', "-")).into());
+
+            std::fs::write(&output, mbox).map_err(EmailError::Output)?;
+            println!("{}", output.display());
+        }
+
+        Ok(())
+    }
+}
+
+fn get_blurb(cli: &EmailCli) -> Result<Option<String>, EmailError> {
+    let Some(desc) = &cli.description else {
+        return Ok(None);
+    };
+
+    match desc.read() {
+        Ok(s) => Ok(Some(s)),
+        Err(_) => Err(EmailError::FailedDescription),
+    }
+}
+
+/// Recipients given on the command line, falling back to `sendemail.<key>`
+fn config_default(given: &[String], snapshot: &gix::config::Snapshot, key: &str) -> Vec<String> {
+    if !given.is_empty() {
+        return given.to_vec();
+    }
+
+    snapshot
+        .strings("sendemail", None, key)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn sendmail_message(sendmail: &str, message: &str) -> Result<(), EmailError> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(sendmail)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(EmailError::Sendmail)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(message.as_bytes())
+            .map_err(EmailError::Sendmail)?;
+    }
+
+    let status = child.wait().map_err(EmailError::Sendmail)?;
+    if !status.success() {
+        return Err(EmailError::SendmailFailed(status));
+    }
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl Runner for Email {
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.send_series(repo, req, args).await?;
+
+        Ok(())
+    }
+}
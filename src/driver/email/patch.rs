@@ -0,0 +1,147 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::EmailError;
+
+/// Options passed along to `git format-patch`
+pub struct Series<'a> {
+    pub range: String,
+    pub cover: Option<(&'a str, &'a str)>,
+    pub to: &'a [String],
+    pub cc: &'a [String],
+    pub reroll_count: Option<u32>,
+    pub subject_prefix: Option<&'a str>,
+}
+
+const SUBJECT_PLACEHOLDER: &str = "*** SUBJECT HERE ***";
+const BLURB_PLACEHOLDER: &str = "*** BLURB HERE ***";
+
+/// Render the series as a mbox with `git format-patch`
+pub fn format_patch(dir: &Path, series: &Series) -> Result<String, EmailError> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir)
+        .arg("format-patch")
+        .arg("--stdout")
+        .arg("--thread");
+
+    if series.cover.is_some() {
+        cmd.arg("--cover-letter");
+    }
+
+    if let Some(count) = series.reroll_count {
+        cmd.arg(format!("--reroll-count={count}"));
+    }
+
+    if let Some(prefix) = series.subject_prefix {
+        cmd.arg(format!("--subject-prefix={prefix}"));
+    }
+
+    for to in series.to {
+        cmd.arg(format!("--to={to}"));
+    }
+
+    for cc in series.cc {
+        cmd.arg(format!("--cc={cc}"));
+    }
+
+    cmd.arg(&series.range);
+
+    let output = cmd.output().map_err(EmailError::Git)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(EmailError::FormatPatch(stderr.trim().to_string()));
+    }
+
+    let mbox = String::from_utf8_lossy(&output.stdout).to_string();
+    if mbox.is_empty() {
+        return Err(EmailError::NoPatches);
+    }
+
+    if let Some((subject, blurb)) = series.cover {
+        return Ok(fill_cover(&mbox, subject, blurb));
+    }
+
+    Ok(mbox)
+}
+
+/// The mbox with the placeholders of the cover letter replaced by `subject` and `blurb`
+fn fill_cover(mbox: &str, subject: &str, blurb: &str) -> String {
+    mbox.replacen(SUBJECT_PLACEHOLDER, subject, 1)
+        .replacen(BLURB_PLACEHOLDER, blurb, 1)
+}
+
+/// Split a mbox into its messages, dropping the `From <sha> <date>` separators
+pub fn messages(mbox: &str) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in mbox.split_inclusive('\n') {
+        if line.starts_with("From ") && line.trim_end().ends_with(" Mon Sep 17 00:00:00 2001") {
+            messages.extend(current.take());
+            current = Some(String::new());
+            continue;
+        }
+
+        if let Some(message) = &mut current {
+            message.push_str(line);
+        }
+    }
+
+    messages.extend(current);
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_cover, messages};
+
+    const MBOX: &str = "\
+From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: A U Thor <a@example.com>
+Subject: [PATCH 0/2] *** SUBJECT HERE ***
+
+*** BLURB HERE ***
+
+A U Thor (2):
+  Add parser
+  From the top, handle empty input
+
+From 1111111111111111111111111111111111111111 Mon Sep 17 00:00:00 2001
+From: A U Thor <a@example.com>
+Subject: [PATCH 1/2] Add parser
+
+From the start of a line, but not a separator.
+---
+From 2222222222222222222222222222222222222222 Mon Sep 17 00:00:00 2001
+From: A U Thor <a@example.com>
+Subject: [PATCH 2/2] From the top, handle empty input
+
+---
+";
+
+    #[test]
+    fn split_messages() {
+        let messages = messages(MBOX);
+
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].starts_with("From: A U Thor"));
+        assert!(messages[1].contains("Subject: [PATCH 1/2] Add parser\n"));
+        assert!(messages[1].contains("From the start of a line, but not a separator.\n"));
+        assert!(messages[2].ends_with("---\n"));
+    }
+
+    #[test]
+    fn no_messages() {
+        assert!(messages("").is_empty());
+    }
+
+    #[test]
+    fn cover_placeholders() {
+        let filled = fill_cover(MBOX, "Parser", "Parses things.");
+
+        assert!(filled.contains("Subject: [PATCH 0/2] Parser\n"));
+        assert!(filled.contains("\nParses things.\n"));
+        assert!(!filled.contains("*** SUBJECT HERE ***"));
+        assert!(!filled.contains("*** BLURB HERE ***"));
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use gix::bstr::{BString, ByteSlice};
+use gix::refs::transaction::PreviousValue;
+use gix::{ObjectId, Repository};
+
+use super::GerritError;
+
+const TRAILER: &str = "Change-Id:";
+
+fn rewrite_error(e: impl std::error::Error + Send + Sync + 'static) -> GerritError {
+    GerritError::Rewrite(Box::new(e))
+}
+
+/// Make sure every commit in `commits` (oldest first) carries a `Change-Id` trailer,
+/// rewriting the ones that don't and moving `branch` to the rewritten tip.
+///
+/// The Change-Id is derived from the original commit id so it stays unique per change.
+/// Signed commits are not rewritten since that would drop their signature.
+pub fn ensure(repo: &Repository, branch: &str, commits: &[ObjectId]) -> Result<(), GerritError> {
+    let Some(tip) = commits.last().copied() else {
+        return Err(GerritError::NoCommits);
+    };
+
+    let mut rewritten: HashMap<ObjectId, ObjectId> = HashMap::new();
+
+    for id in commits {
+        let object = repo.find_object(*id).map_err(rewrite_error)?;
+        let commit = object.try_into_commit().map_err(rewrite_error)?;
+        let mut commit: gix::objs::Commit = commit.decode().map_err(rewrite_error)?.into();
+
+        let has_change_id = has_change_id(commit.message.as_bstr());
+        let reparented = commit.parents.iter().any(|p| rewritten.contains_key(p));
+
+        if has_change_id && !reparented {
+            continue;
+        }
+
+        if !has_change_id {
+            commit.message = add_change_id(commit.message.as_bstr(), id);
+        }
+
+        // the signature is over the original content, refuse instead of silently dropping it
+        if commit
+            .extra_headers
+            .iter()
+            .any(|(name, _)| name == "gpgsig")
+        {
+            return Err(GerritError::Signed(*id));
+        }
+
+        commit.parents = commit
+            .parents
+            .iter()
+            .map(|p| *rewritten.get(p).unwrap_or(p))
+            .collect();
+
+        let new = repo.write_object(&commit).map_err(rewrite_error)?.detach();
+        rewritten.insert(*id, new);
+    }
+
+    if let Some(new_tip) = rewritten.get(&tip) {
+        repo.reference(
+            format!("refs/heads/{branch}"),
+            *new_tip,
+            PreviousValue::MustExistAndMatch(tip.into()),
+            "tidal: add Change-Id",
+        )
+        .map_err(rewrite_error)?;
+
+        log::info!("added Change-Id to {} commits", rewritten.len());
+    }
+
+    Ok(())
+}
+
+fn has_change_id(message: &gix::bstr::BStr) -> bool {
+    message
+        .lines()
+        .any(|line| line.starts_with(TRAILER.as_bytes()))
+}
+
+fn add_change_id(message: &gix::bstr::BStr, id: &ObjectId) -> BString {
+    let trimmed = message.trim_end();
+    let last_paragraph = trimmed.rsplit_str("\n\n").next().unwrap_or_default();
+
+    let is_trailers = trimmed.contains_str("\n\n")
+        && last_paragraph.lines().all(|line| {
+            line.find(": ")
+                .map(|i| !line[..i].contains(&b' '))
+                .unwrap_or(false)
+        });
+
+    let mut message = BString::from(trimmed);
+    if is_trailers {
+        message.push(b'\n');
+    } else {
+        message.extend_from_slice(b"\n\n");
+    }
+
+    message.extend_from_slice(format!("{TRAILER} I{id}\n").as_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use gix::bstr::ByteSlice;
+    use gix::ObjectId;
+
+    use super::{add_change_id, has_change_id};
+
+    fn id() -> ObjectId {
+        ObjectId::from_hex(b"0123456789abcdef0123456789abcdef01234567").unwrap()
+    }
+
+    fn added(message: &str) -> String {
+        add_change_id(message.as_bytes().as_bstr(), &id()).to_string()
+    }
+
+    const CHANGE_ID: &str = "Change-Id: I0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn subject_only() {
+        assert_eq!(
+            added("docs: fix typo\n"),
+            format!("docs: fix typo\n\n{CHANGE_ID}\n")
+        );
+    }
+
+    #[test]
+    fn existing_trailers() {
+        assert_eq!(
+            added("Fix parser\n\nHandle empty input.\n\nSigned-off-by: A U Thor <a@example.com>\n"),
+            format!(
+                "Fix parser\n\nHandle empty input.\n\nSigned-off-by: A U Thor <a@example.com>\n{CHANGE_ID}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn existing_change_id() {
+        let message = format!("Fix parser\n\n{CHANGE_ID}\n");
+        assert!(has_change_id(message.as_bytes().as_bstr()));
+        assert!(!has_change_id(
+            b"Fix parser\n\nMentions Change-Id: inline\n".as_bstr()
+        ));
+    }
+
+    #[test]
+    fn colon_in_last_paragraph() {
+        assert_eq!(
+            added("Fix parser\n\nThe cause: an off by one.\n"),
+            format!("Fix parser\n\nThe cause: an off by one.\n\n{CHANGE_ID}\n")
+        );
+        assert_eq!(
+            added("Fix parser\n\nFixes: #12\nand some prose\n"),
+            format!("Fix parser\n\nFixes: #12\nand some prose\n\n{CHANGE_ID}\n")
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use crate::tidal::Common;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version)]
+pub struct GerritCli {
+    /// Topic of the change
+    #[clap(short, long)]
+    pub topic: Option<String>,
+
+    /// Reviewer of the change (repeatable)
+    #[clap(short = 'v', long = "reviewer")]
+    pub reviewers: Vec<String>,
+
+    /// Carbon copy for the change (repeatable)
+    #[clap(short, long)]
+    pub cc: Vec<String>,
+
+    /// Hashtag for the change (repeatable)
+    #[clap(short = 'H', long = "hashtag")]
+    pub hashtags: Vec<String>,
+
+    /// Work in progress change
+    #[clap(short = 'D', long)]
+    pub wip: bool,
+
+    /// Open page for change
+    #[clap(short, long)]
+    pub open: bool,
+
+    #[clap(flatten)]
+    pub common: Common,
+}
@@ -0,0 +1,43 @@
+use crate::errors::Error;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum GerritError {
+    Git(std::io::Error),
+    GitFailed(String),
+    Rewrite(Box<dyn std::error::Error + Send + Sync>),
+    NoCommits,
+    Signed(gix::ObjectId),
+    PushFlags,
+    FailedToOpen,
+}
+
+impl std::error::Error for GerritError {}
+
+impl Display for GerritError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GerritError::Git(e) => write!(f, "failed to run git: {e}")?,
+            GerritError::GitFailed(e) => write!(f, "git failed: {e}")?,
+            GerritError::Rewrite(e) => write!(f, "failed to add Change-Id: {e}")?,
+            GerritError::NoCommits => write!(f, "no commits to push for review")?,
+            GerritError::Signed(id) => write!(
+                f,
+                "commit {id} is signed and would need a new Change-Id, add it with gerrit's commit-msg hook"
+            )?,
+            GerritError::PushFlags => write!(
+                f,
+                "--push, --no-push and --force-with-lease do not apply, changes are always pushed for review"
+            )?,
+            GerritError::FailedToOpen => write!(f, "failed to open change in browser")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for GerritError {
+    fn status(&self) -> i32 {
+        10
+    }
+}
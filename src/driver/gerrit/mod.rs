@@ -0,0 +1,73 @@
+use std::ffi::OsString;
+
+use super::{DriverError, Runner};
+use crate::driver::gerrit::cli::GerritCli;
+use crate::driver::gerrit::push::PushOptions;
+use crate::errors::SysErrorHandler;
+use crate::tidal::Req;
+use clap::Parser;
+
+pub use err::GerritError;
+use gix::Repository;
+
+mod change_id;
+mod cli;
+mod err;
+mod push;
+
+/// Upload changes for review by pushing to gerrits [magic refs](https://gerrit-review.googlesource.com/Documentation/user-upload.html#push_create)
+pub struct Gerrit;
+
+impl Gerrit {
+    async fn upload_change(
+        &self,
+        repo: Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), GerritError> {
+        let cli = GerritCli::parse_from(args);
+        if cli.common.push || cli.common.no_push || cli.common.force_with_lease {
+            return Err(GerritError::PushFlags);
+        }
+
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+
+        let dir = repo.work_dir().unwrap_or(repo.git_dir());
+
+        let commits = push::commits(dir, &req.from.branch, &req.to.branch)?;
+        change_id::ensure(&repo, &req.from.branch, &commits)?;
+
+        let opts = PushOptions {
+            topic: cli.topic.as_deref(),
+            reviewers: &cli.reviewers,
+            cc: &cli.cc,
+            hashtags: &cli.hashtags,
+            wip: cli.wip,
+        };
+
+        let urls = push::push(dir, &req.to.remote, &req.from.branch, &req.to.branch, &opts)?;
+
+        if cli.open {
+            for url in urls {
+                open::that(url).map_err(|_| GerritError::FailedToOpen)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Runner for Gerrit {
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.upload_change(repo, req, args).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+use std::process::Command;
+
+use gix::ObjectId;
+
+use super::GerritError;
+
+/// Options appended to the `refs/for/<branch>` refspec
+pub struct PushOptions<'a> {
+    pub topic: Option<&'a str>,
+    pub reviewers: &'a [String],
+    pub cc: &'a [String],
+    pub hashtags: &'a [String],
+    pub wip: bool,
+}
+
+impl PushOptions<'_> {
+    fn refspec(&self, from: &str, to: &str) -> String {
+        let mut opts = Vec::new();
+
+        if let Some(topic) = self.topic {
+            opts.push(format!("topic={}", encode(topic)));
+        }
+
+        opts.extend(self.reviewers.iter().map(|r| format!("r={r}")));
+        opts.extend(self.cc.iter().map(|cc| format!("cc={cc}")));
+        opts.extend(
+            self.hashtags
+                .iter()
+                .map(|t| format!("hashtag={}", encode(t))),
+        );
+
+        if self.wip {
+            opts.push("wip".to_string());
+        }
+
+        if opts.is_empty() {
+            format!("refs/heads/{from}:refs/for/{to}")
+        } else {
+            format!("refs/heads/{from}:refs/for/{to}%{}", opts.join(","))
+        }
+    }
+}
+
+/// Percent-encode a value of a push option, so spaces, commas and `%` don't end it
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+fn git(dir: &Path) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir);
+    cmd
+}
+
+/// Commits in `to..from`, oldest first
+pub fn commits(dir: &Path, from: &str, to: &str) -> Result<Vec<ObjectId>, GerritError> {
+    let output = git(dir)
+        .args(["rev-list", "--reverse", "--topo-order"])
+        .arg(format!("{to}..{from}"))
+        .output()
+        .map_err(GerritError::Git)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GerritError::GitFailed(stderr.trim().to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let commits = stdout
+        .lines()
+        .filter_map(|line| ObjectId::from_hex(line.trim().as_bytes()).ok())
+        .collect();
+
+    Ok(commits)
+}
+
+/// Push `from` for review on `to`, returning the change urls gerrit reported
+pub fn push(
+    dir: &Path,
+    remote: &str,
+    from: &str,
+    to: &str,
+    opts: &PushOptions,
+) -> Result<Vec<String>, GerritError> {
+    let output = git(dir)
+        .arg("push")
+        .arg(remote)
+        .arg(opts.refspec(from, to))
+        .output()
+        .map_err(GerritError::Git)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    eprint!("{stderr}");
+
+    if !output.status.success() {
+        return Err(GerritError::GitFailed("push was rejected".to_string()));
+    }
+
+    let urls = stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix("remote:"))
+        .filter_map(|line| line.split_whitespace().find(|w| w.starts_with("http")))
+        .map(|url| url.to_string())
+        .collect();
+
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PushOptions;
+
+    fn options<'a>(topic: Option<&'a str>, hashtags: &'a [String]) -> PushOptions<'a> {
+        PushOptions {
+            topic,
+            reviewers: &[],
+            cc: &[],
+            hashtags,
+            wip: false,
+        }
+    }
+
+    #[test]
+    fn plain_refspec() {
+        assert_eq!(
+            options(None, &[]).refspec("feature", "main"),
+            "refs/heads/feature:refs/for/main"
+        );
+    }
+
+    #[test]
+    fn encoded_values() {
+        let hashtags = ["release notes".to_string(), "a,b".to_string()];
+        let opts = options(Some("fix 100%"), &hashtags);
+
+        assert_eq!(
+            opts.refspec("feature", "main"),
+            "refs/heads/feature:refs/for/main%topic=fix%20100%25,hashtag=release%20notes,hashtag=a%2Cb"
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use crate::tidal::{Common, Desc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version)]
+pub struct GiteaCli {
+    /// Title of the pull request
+    #[clap(short, long)]
+    pub title: String,
+
+    /// Description of pull request
+    #[clap(short, long)]
+    pub description: Option<Desc>,
+
+    /// Open page for pull request
+    #[clap(short, long)]
+    pub open: bool,
+
+    /// Work in progress pull request
+    #[clap(short = 'D', long)]
+    pub draft: bool,
+
+    /// User to assign the pull request to (repeatable)
+    #[clap(short, long = "assignee")]
+    pub assignees: Vec<String>,
+
+    /// Id of a label to add to the pull request (repeatable)
+    #[clap(short, long = "label")]
+    pub labels: Vec<u64>,
+
+    #[clap(flatten)]
+    pub common: Common,
+}
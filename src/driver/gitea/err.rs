@@ -0,0 +1,55 @@
+use crate::{cred::CredError, errors::Error};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum GiteaError {
+    FailedDescription,
+    Http(reqwest::Error),
+    Forbidden,
+    NotFound,
+    Conflict,
+    Validation,
+    NoToken(CredError),
+    FailedToOpen,
+}
+
+impl From<reqwest::Error> for GiteaError {
+    fn from(value: reqwest::Error) -> Self {
+        if let Some(status) = value.status() {
+            match status.into() {
+                403 => return GiteaError::Forbidden,
+                404 => return GiteaError::NotFound,
+                409 => return GiteaError::Conflict,
+                422 => return GiteaError::Validation,
+                _ => (),
+            }
+        }
+
+        GiteaError::Http(value)
+    }
+}
+
+impl std::error::Error for GiteaError {}
+
+impl Display for GiteaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GiteaError::Forbidden => write!(f, "not permitted to create pull request")?,
+            GiteaError::NotFound => write!(f, "repository or branch not found")?,
+            GiteaError::Conflict => write!(f, "pull request already exists")?,
+            GiteaError::Validation => write!(f, "pull request failed validation")?,
+            GiteaError::FailedDescription => write!(f, "failed to read description")?,
+            GiteaError::NoToken(e) => write!(f, "{e}")?,
+            GiteaError::Http(e) => write!(f, "http error: {e}")?,
+            GiteaError::FailedToOpen => write!(f, "failed to open pull request in browser")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for GiteaError {
+    fn status(&self) -> i32 {
+        4
+    }
+}
@@ -0,0 +1,174 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use super::{DriverError, Runner};
+use crate::cred::find_token;
+use crate::driver::gitea::cli::GiteaCli;
+use crate::driver::gitea::req::{CreatePullRequest, ListPulls};
+use crate::errors::SysErrorHandler;
+use crate::tidal::{ListFilter, PullRequest, Req, StateFilter};
+use clap::Parser;
+
+pub use err::GiteaError;
+use gix::remote::Direction;
+use gix::Repository;
+
+mod cli;
+mod err;
+mod req;
+
+const PAGE_SIZE: usize = 50;
+
+/// Create a pull request with giteas (and forgejos) [pull request api](https://gitea.com/api/swagger#/repository/repoCreatePullRequest)
+pub struct Gitea {
+    /// Base use
+    pub base: String,
+}
+
+impl Gitea {
+    async fn create_pull_request(
+        &self,
+        repo: Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), GiteaError> {
+        let cli = GiteaCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+        req.push(&repo, &cli.common).handle_system_error();
+
+        let title = get_title(&cli);
+        let body = get_body(&cli)?;
+
+        let (our, _) = repo_parts(&repo, &req.from.remote)?;
+        let (owner, owner_repo) = repo_parts(&repo, &req.to.remote)?;
+
+        let head = if our == owner {
+            req.from.branch
+        } else {
+            format!("{}:{}", our, req.from.branch)
+        };
+
+        let payload = CreatePullRequest {
+            title,
+            head,
+            base: req.to.branch,
+            body,
+            assignees: cli.assignees,
+            labels: cli.labels,
+        };
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(GiteaError::NoToken)?;
+        let res =
+            req::send_request(&client, &self.base, &owner, &owner_repo, &token, payload).await?;
+
+        if cli.open {
+            open::that(res.html_url).map_err(|_| GiteaError::FailedToOpen)?;
+        }
+
+        Ok(())
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, GiteaError> {
+        let (owner, owner_repo) = repo_parts(&repo, &req.to.remote)?;
+
+        let state = match filter.state {
+            StateFilter::Open => "open",
+            StateFilter::Closed | StateFilter::Merged => "closed",
+            StateFilter::All => "all",
+        };
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(GiteaError::NoToken)?;
+
+        let mut prs = Vec::new();
+        for page in 1.. {
+            let query = ListPulls {
+                state,
+                limit: PAGE_SIZE,
+                page,
+            };
+
+            let batch =
+                req::list_pulls(&client, &self.base, &owner, &owner_repo, &token, &query).await?;
+            let last = batch.len() < PAGE_SIZE;
+
+            prs.extend(
+                batch
+                    .into_iter()
+                    .map(PullRequest::from)
+                    .filter(|pr| filter.matches(pr)),
+            );
+
+            if last || prs.len() >= filter.limit {
+                break;
+            }
+        }
+
+        prs.truncate(filter.limit);
+        Ok(prs)
+    }
+}
+
+fn get_title(cli: &GiteaCli) -> String {
+    if cli.draft {
+        format!("WIP: {}", cli.title)
+    } else {
+        cli.title.clone()
+    }
+}
+
+fn get_body(cli: &GiteaCli) -> Result<String, GiteaError> {
+    let body = if let Some(desc) = &cli.description {
+        match desc.read() {
+            Ok(s) => s,
+            Err(_) => return Err(GiteaError::FailedDescription),
+        }
+    } else {
+        "".to_string()
+    };
+    Ok(body)
+}
+
+fn repo_parts(repo: &Repository, remote: &str) -> Result<(String, String), GiteaError> {
+    let remote = repo.find_remote(remote).unwrap();
+    let url = remote.url(Direction::Push).unwrap();
+
+    let mut path = PathBuf::from(url.path.to_string());
+
+    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    let repo = name.strip_suffix(".git").unwrap_or(&name).to_string();
+    path.pop();
+    let owner = path.file_name().unwrap().to_string_lossy().to_string();
+
+    Ok((owner, repo))
+}
+
+#[async_trait::async_trait]
+impl Runner for Gitea {
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.create_pull_request(repo, req, args).await?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, DriverError> {
+        Ok(self.list_pull_requests(repo, req, filter).await?)
+    }
+}
@@ -0,0 +1,141 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::GiteaError;
+use crate::tidal::State;
+
+#[derive(Serialize, Debug)]
+pub struct CreatePullRequest {
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    pub body: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatePullRequestResponse {
+    pub html_url: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ListPulls<'a> {
+    pub state: &'a str,
+    pub limit: usize,
+    pub page: usize,
+}
+
+#[derive(Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub user: User,
+    pub head: Branch,
+    pub base: Branch,
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub merged: bool,
+    pub labels: Vec<Label>,
+    pub html_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Deserialize)]
+pub struct Branch {
+    #[serde(rename = "ref")]
+    pub branch: String,
+}
+
+#[derive(Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+impl From<PullRequest> for crate::tidal::PullRequest {
+    fn from(pr: PullRequest) -> Self {
+        let state = match (pr.state.as_str(), pr.merged) {
+            ("open", _) => State::Open,
+            (_, true) => State::Merged,
+            _ => State::Closed,
+        };
+
+        let draft = pr.draft || pr.title.starts_with("WIP:") || pr.title.starts_with("[WIP]");
+
+        crate::tidal::PullRequest {
+            number: pr.number,
+            title: pr.title,
+            author: pr.user.login,
+            head: pr.head.branch,
+            base: pr.base.branch,
+            state,
+            draft,
+            labels: pr.labels.into_iter().map(|l| l.name).collect(),
+            url: pr.html_url,
+        }
+    }
+}
+
+pub async fn send_request(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    req: CreatePullRequest,
+) -> Result<CreatePullRequestResponse, GiteaError> {
+    let url = format!("https://{base}/api/v1/repos/{owner}/{repo}/pulls");
+
+    let req = client
+        .post(url)
+        .header("User-Agent", "Tidal")
+        .header("Accept", "application/json")
+        .header("Authorization", format!("token {}", token))
+        .json(&req);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: CreatePullRequestResponse = res.json().await?;
+
+    Ok(res)
+}
+
+pub async fn list_pulls(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    query: &ListPulls<'_>,
+) -> Result<Vec<PullRequest>, GiteaError> {
+    let url = format!("https://{base}/api/v1/repos/{owner}/{repo}/pulls");
+
+    let req = client
+        .get(url)
+        .header("User-Agent", "Tidal")
+        .header("Accept", "application/json")
+        .header("Authorization", format!("token {}", token))
+        .query(query);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: Vec<PullRequest> = res.json().await?;
+
+    Ok(res)
+}
@@ -7,10 +7,15 @@ pub struct GithubCli {
     #[command(flatten)]
     pub id: Id,
 
-    /// Description of pull request
+    /// Description of pull request (`-` for stdin, `@file`, `@edit` for the editor,
+    /// `@./edit` for a file named edit)
     #[clap(short, long)]
     pub description: Option<Desc>,
 
+    /// Pull request template to use from a PULL_REQUEST_TEMPLATE directory
+    #[clap(short = 'T', long)]
+    pub template: Option<String>,
+
     /// Open page for pull request
     #[clap(short, long)]
     pub open: bool,
@@ -23,14 +28,59 @@ pub struct GithubCli {
     #[clap(short, long)]
     pub fixup: bool,
 
+    /// Update the branch's pull request if there is one, with the title, description,
+    /// template, base (--to-branch) and draft state given
+    #[clap(short, long)]
+    pub update: bool,
+
+    /// Mark the pull request ready for review when updating it
+    #[clap(long, requires = "update", conflicts_with = "draft")]
+    pub ready: bool,
+
+    /// Fork the repository if you can't push to it, and open the pull request from the fork
+    #[clap(long)]
+    pub fork: bool,
+
+    /// Open the pull request within a fork instead of against the repository it was forked from
+    #[clap(long)]
+    pub in_fork: bool,
+
+    /// Create a pull request for every branch of the stack the branch is part of,
+    /// titled and described by their commits
+    #[clap(short, long, conflicts_with_all = ["title", "issue", "description", "template", "fork", "update", "open"])]
+    pub stack: bool,
+
+    /// User to request a review from (repeatable, defaults to tidal.reviewers)
+    #[clap(short = 'v', long = "reviewer")]
+    pub reviewers: Vec<String>,
+
+    /// Team to request a review from (repeatable)
+    #[clap(long = "team-reviewer")]
+    pub team_reviewers: Vec<String>,
+
+    /// User to assign the pull request to (repeatable)
+    #[clap(short, long = "assignee")]
+    pub assignees: Vec<String>,
+
+    /// Label to add to the pull request (repeatable, defaults to tidal.labels)
+    #[clap(short, long = "label")]
+    pub labels: Vec<String>,
+
+    /// Title of the milestone to add the pull request to (defaults to tidal.milestone)
+    #[clap(short, long)]
+    pub milestone: Option<String>,
+
+    /// Project board to add the pull request to as [owner/]number (defaults to tidal.project)
+    #[clap(short, long)]
+    pub project: Option<String>,
+
     #[clap(flatten)]
     pub common: Common,
 }
 
 #[derive(Args, Debug)]
-#[group(required = true)]
 pub struct Id {
-    /// Title of the pull request
+    /// Title of the pull request (defaults to the commit subject or branch name)
     #[clap(short, long)]
     pub title: Option<String>,
 
@@ -0,0 +1,62 @@
+use gix::Repository;
+use reqwest::Client;
+
+use super::{graphql, req, Github, GithubError, Remotes};
+use crate::tidal::{Conversation, PullNumber, Req};
+
+impl Github {
+    pub(super) async fn comment_on_pull_request(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        body: String,
+    ) -> Result<String, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        let number = self
+            .pull_number(&client, &token, &owner, &owner_repo, &head, number)
+            .await?;
+
+        let payload = req::AddComment { body };
+        let res = req::add_comment(
+            &client,
+            &self.base,
+            &owner,
+            &owner_repo,
+            &token,
+            number,
+            payload,
+        )
+        .await?;
+
+        Ok(res.html_url)
+    }
+
+    pub(super) async fn pull_request_conversation(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Conversation, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        let number = self
+            .pull_number(&client, &token, &owner, &owner_repo, &head, number)
+            .await?;
+
+        graphql::conversation(&client, &self.base, &token, &owner, &owner_repo, number).await
+    }
+}
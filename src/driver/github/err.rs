@@ -1,4 +1,4 @@
-use crate::{errors::Error, tidal::Desc};
+use crate::{cred::CredError, errors::Error, tidal::Desc};
 use std::fmt::Display;
 
 #[derive(Debug)]
@@ -7,8 +7,19 @@ pub enum GithubError {
     Http(reqwest::Error),
     Forbidden,
     Validation,
-    NoToken,
+    NoToken(CredError),
     FailedToOpen,
+    NoTemplate(String),
+    NoMilestone(String),
+    NoProject(String),
+    GraphQL(String),
+    NoPullRequest(String),
+    NotMergeable(String),
+    HeadChanged,
+    Exists(u64),
+    OtherRepository(String, String),
+    NoRemoteUrl(String),
+    ForkTimeout(String),
 }
 
 impl From<reqwest::Error> for GithubError {
@@ -33,9 +44,25 @@ impl Display for GithubError {
             GithubError::Forbidden => write!(f, "not permitted to create pull request")?,
             GithubError::Validation => write!(f, "pull request failed validation")?,
             GithubError::FailedDescription(_) => write!(f, "failed to read description")?,
-            GithubError::NoToken => write!(f, "no token found")?,
+            GithubError::NoToken(e) => write!(f, "{e}")?,
             GithubError::Http(e) => write!(f, "http error: {e}")?,
             GithubError::FailedToOpen => write!(f, "failed to open pull request in browser")?,
+            GithubError::NoTemplate(name) => write!(f, "no pull request template named {name}")?,
+            GithubError::NoMilestone(title) => write!(f, "no open milestone named {title}")?,
+            GithubError::NoProject(project) => write!(f, "no project {project}")?,
+            GithubError::GraphQL(e) => write!(f, "graphql error: {e}")?,
+            GithubError::NoPullRequest(head) => write!(f, "no open pull request from {head}")?,
+            GithubError::NotMergeable(e) => write!(f, "pull request is not mergeable: {e}")?,
+            GithubError::HeadChanged => write!(f, "head of the pull request has changed")?,
+            GithubError::OtherRepository(url, repo) => {
+                write!(f, "pull request is in {url}, not in {repo}")?
+            }
+            GithubError::NoRemoteUrl(remote) => write!(f, "remote {remote} has no url")?,
+            GithubError::ForkTimeout(fork) => write!(f, "fork {fork} wasn't ready in time")?,
+            GithubError::Exists(number) => write!(
+                f,
+                "pull request #{number} already exists, use --update to update it"
+            )?,
         }
 
         Ok(())
@@ -44,6 +71,9 @@ impl Display for GithubError {
 
 impl Error for GithubError {
     fn status(&self) -> i32 {
-        2
+        match self {
+            GithubError::Exists(_) => 17,
+            _ => 2,
+        }
     }
 }
@@ -0,0 +1,490 @@
+use reqwest::Client;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+
+use super::{req::github, GithubError};
+use crate::tidal::{Comment, Conversation, Thread};
+
+#[derive(Serialize)]
+struct Query<'a, V> {
+    query: &'a str,
+    variables: V,
+}
+
+#[derive(Deserialize)]
+struct Response<T> {
+    data: Option<T>,
+    errors: Option<Vec<Message>>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    message: String,
+}
+
+/// The graphql endpoint next to the rest api,
+/// `api.github.com` serves it at `/graphql` and enterprise servers at `/api/graphql`
+fn url(base: &str) -> String {
+    match base.strip_suffix("/api/v3") {
+        Some(host) => format!("https://{host}/api/graphql"),
+        None => format!("https://{base}/graphql"),
+    }
+}
+
+pub async fn query<V: Serialize, T: DeserializeOwned>(
+    client: &Client,
+    base: &str,
+    token: &str,
+    query: &str,
+    variables: V,
+) -> Result<T, GithubError> {
+    let req = github(client.post(url(base)), token).json(&Query { query, variables });
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    let res: Response<T> = res.json().await?;
+
+    if let Some(errors) = res.errors {
+        let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
+        return Err(GithubError::GraphQL(messages.join(", ")));
+    }
+
+    res.data
+        .ok_or_else(|| GithubError::GraphQL("empty response".to_string()))
+}
+
+const FIND_PROJECT: &str = "
+query($owner: String!, $number: Int!) {
+  repositoryOwner(login: $owner) {
+    ... on ProjectV2Owner {
+      projectV2(number: $number) { id }
+    }
+  }
+}";
+
+const ADD_TO_PROJECT: &str = "
+mutation($project: ID!, $content: ID!) {
+  addProjectV2ItemById(input: { projectId: $project, contentId: $content }) {
+    item { id }
+  }
+}";
+
+const ENABLE_AUTO_MERGE: &str = "
+mutation($pull: ID!, $method: PullRequestMergeMethod!, $title: String, $body: String, $head: GitObjectID) {
+  enablePullRequestAutoMerge(input: {
+    pullRequestId: $pull,
+    mergeMethod: $method,
+    commitHeadline: $title,
+    commitBody: $body,
+    expectedHeadOid: $head
+  }) {
+    clientMutationId
+  }
+}";
+
+const CONVERT_TO_DRAFT: &str = "
+mutation($pull: ID!) {
+  convertPullRequestToDraft(input: { pullRequestId: $pull }) {
+    clientMutationId
+  }
+}";
+
+const MARK_READY: &str = "
+mutation($pull: ID!) {
+  markPullRequestReadyForReview(input: { pullRequestId: $pull }) {
+    clientMutationId
+  }
+}";
+
+/// One page of the connection `{field}` of a pull request, aliased so every field reads alike
+fn pull_connection(field: &str, nodes: &str) -> String {
+    format!(
+        "
+query($owner: String!, $repo: String!, $number: Int!, $cursor: String) {{
+  repository(owner: $owner, name: $repo) {{
+    pullRequest(number: $number) {{
+      connection: {field}(first: 100, after: $cursor) {{
+        pageInfo {{ hasNextPage endCursor }}
+        nodes {{ {nodes} }}
+      }}
+    }}
+  }}
+}}"
+    )
+}
+
+const COMMENT_FIELDS: &str = "author { login } body createdAt";
+
+const REVIEW_FIELDS: &str = "author { login } body state createdAt";
+
+const THREAD_FIELDS: &str = "
+id isResolved isOutdated path line
+comments(first: 100) {
+  pageInfo { hasNextPage endCursor }
+  nodes { author { login } body createdAt }
+}";
+
+const THREAD_COMMENTS: &str = "
+query($thread: ID!, $cursor: String) {
+  node(id: $thread) {
+    ... on PullRequestReviewThread {
+      connection: comments(first: 100, after: $cursor) {
+        pageInfo { hasNextPage endCursor }
+        nodes { author { login } body createdAt }
+      }
+    }
+  }
+}";
+
+#[derive(Serialize)]
+struct PullVars<'a> {
+    pull: &'a str,
+}
+
+#[derive(Serialize)]
+struct FindProjectVars<'a> {
+    owner: &'a str,
+    number: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FindProject {
+    repository_owner: Option<ProjectOwner>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectOwner {
+    project_v2: Option<Node>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct AddToProjectVars<'a> {
+    project: &'a str,
+    content: &'a str,
+}
+
+/// Add a pull request (by node id) to the projects (v2) board `number` of `owner`
+pub async fn add_to_project(
+    client: &Client,
+    base: &str,
+    token: &str,
+    owner: &str,
+    number: u64,
+    content: &str,
+) -> Result<(), GithubError> {
+    let found: FindProject = query(
+        client,
+        base,
+        token,
+        FIND_PROJECT,
+        FindProjectVars { owner, number },
+    )
+    .await?;
+
+    let project = found
+        .repository_owner
+        .and_then(|owner| owner.project_v2)
+        .ok_or_else(|| GithubError::NoProject(format!("{owner}/{number}")))?;
+
+    let _: IgnoredAny = query(
+        client,
+        base,
+        token,
+        ADD_TO_PROJECT,
+        AddToProjectVars {
+            project: &project.id,
+            content,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct AutoMerge<'a> {
+    /// Node id of the pull request
+    pub pull: &'a str,
+    /// `MERGE`, `SQUASH` or `REBASE`
+    pub method: &'a str,
+    pub title: Option<&'a str>,
+    pub body: Option<&'a str>,
+    pub head: Option<&'a str>,
+}
+
+/// Merge a pull request once its requirements are met
+pub async fn enable_auto_merge(
+    client: &Client,
+    base: &str,
+    token: &str,
+    vars: AutoMerge<'_>,
+) -> Result<(), GithubError> {
+    let _: IgnoredAny = query(client, base, token, ENABLE_AUTO_MERGE, vars).await?;
+
+    Ok(())
+}
+
+/// Mark a draft pull request (by node id) as ready for review
+pub async fn mark_ready(
+    client: &Client,
+    base: &str,
+    token: &str,
+    pull: &str,
+) -> Result<(), GithubError> {
+    let _: IgnoredAny = query(client, base, token, MARK_READY, PullVars { pull }).await?;
+
+    Ok(())
+}
+
+/// Turn a pull request (by node id) back into a draft
+pub async fn convert_to_draft(
+    client: &Client,
+    base: &str,
+    token: &str,
+    pull: &str,
+) -> Result<(), GithubError> {
+    let _: IgnoredAny = query(client, base, token, CONVERT_TO_DRAFT, PullVars { pull }).await?;
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ConnectionVars<'a> {
+    owner: &'a str,
+    repo: &'a str,
+    number: u64,
+    cursor: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct ThreadVars<'a> {
+    thread: &'a str,
+    cursor: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct FindConnection<T> {
+    repository: Option<ConnectionRepo<T>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConnectionRepo<T> {
+    pull_request: Option<Connection<T>>,
+}
+
+#[derive(Deserialize)]
+struct FindNode<T> {
+    node: Option<Connection<T>>,
+}
+
+#[derive(Deserialize)]
+struct Connection<T> {
+    connection: Page<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    page_info: PageInfo,
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentNode {
+    /// Missing for deleted accounts
+    author: Option<Author>,
+    body: String,
+    state: Option<String>,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadNode {
+    id: String,
+    is_resolved: bool,
+    is_outdated: bool,
+    path: String,
+    line: Option<u64>,
+    comments: Page<CommentNode>,
+}
+
+impl From<CommentNode> for Comment {
+    fn from(node: CommentNode) -> Self {
+        Comment {
+            author: node.author.map_or_else(|| "ghost".to_string(), |a| a.login),
+            body: node.body,
+            created_at: node.created_at,
+            review: node
+                .state
+                .map(|state| state.to_lowercase().replace('_', " ")),
+        }
+    }
+}
+
+/// All nodes of the connection `field` of pull request `number`, page by page
+#[allow(clippy::too_many_arguments)]
+async fn pull_nodes<T: DeserializeOwned>(
+    client: &Client,
+    base: &str,
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    field: &str,
+    fields: &str,
+) -> Result<Vec<T>, GithubError> {
+    let text = pull_connection(field, fields);
+
+    let mut nodes = Vec::new();
+    let mut cursor = None;
+    loop {
+        let vars = ConnectionVars {
+            owner,
+            repo,
+            number,
+            cursor: cursor.as_deref(),
+        };
+        let found: FindConnection<T> = query(client, base, token, &text, vars).await?;
+
+        let page = found
+            .repository
+            .and_then(|repo| repo.pull_request)
+            .ok_or_else(|| GithubError::NoPullRequest(format!("#{number}")))?
+            .connection;
+
+        nodes.extend(page.nodes);
+        match page.page_info.end_cursor {
+            Some(end) if page.page_info.has_next_page => cursor = Some(end),
+            _ => return Ok(nodes),
+        }
+    }
+}
+
+/// Comments of a review thread, fetching the pages after the first one in `first`
+async fn thread_comments(
+    client: &Client,
+    base: &str,
+    token: &str,
+    thread: &str,
+    first: Page<CommentNode>,
+) -> Result<Vec<CommentNode>, GithubError> {
+    let mut nodes = first.nodes;
+    let mut info = first.page_info;
+
+    while let (true, Some(end)) = (info.has_next_page, info.end_cursor.take()) {
+        let vars = ThreadVars {
+            thread,
+            cursor: Some(&end),
+        };
+        let found: FindNode<CommentNode> =
+            query(client, base, token, THREAD_COMMENTS, vars).await?;
+
+        let Some(node) = found.node else {
+            break;
+        };
+
+        nodes.extend(node.connection.nodes);
+        info = node.connection.page_info;
+    }
+
+    Ok(nodes)
+}
+
+/// Comments, review summaries and review threads of pull request `number`
+pub async fn conversation(
+    client: &Client,
+    base: &str,
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> Result<Conversation, GithubError> {
+    let issue_comments: Vec<CommentNode> = pull_nodes(
+        client,
+        base,
+        token,
+        owner,
+        repo,
+        number,
+        "comments",
+        COMMENT_FIELDS,
+    )
+    .await?;
+    let reviews: Vec<CommentNode> = pull_nodes(
+        client,
+        base,
+        token,
+        owner,
+        repo,
+        number,
+        "reviews",
+        REVIEW_FIELDS,
+    )
+    .await?;
+    let review_threads: Vec<ThreadNode> = pull_nodes(
+        client,
+        base,
+        token,
+        owner,
+        repo,
+        number,
+        "reviewThreads",
+        THREAD_FIELDS,
+    )
+    .await?;
+
+    // reviews without a summary only carry their threads
+    let mut comments: Vec<Comment> = issue_comments
+        .into_iter()
+        .chain(
+            reviews
+                .into_iter()
+                .filter(|review| !review.body.trim().is_empty()),
+        )
+        .map(Comment::from)
+        .collect();
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let mut threads = Vec::new();
+    for thread in review_threads {
+        let comments = thread_comments(client, base, token, &thread.id, thread.comments).await?;
+
+        threads.push(Thread {
+            path: thread.path,
+            line: thread.line,
+            resolved: thread.is_resolved,
+            outdated: thread.is_outdated,
+            comments: comments.into_iter().map(Comment::from).collect(),
+        });
+    }
+
+    Ok(Conversation { comments, threads })
+}
@@ -0,0 +1,128 @@
+use gix::Repository;
+use reqwest::Client;
+
+use super::{graphql, req, Github, GithubError, Remotes};
+use crate::tidal::{CheckState, MergeOptions, Merged, PullNumber, Req, Strategy};
+
+impl Github {
+    pub(super) async fn merge_pull_request(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        opts: MergeOptions,
+    ) -> Result<Merged, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        let number = self
+            .pull_number(&client, &token, &owner, &owner_repo, &head, number)
+            .await?;
+
+        let pr = req::get_pull(&client, &self.base, &owner, &owner_repo, &token, number).await?;
+        let branch = pr.pull.head.branch;
+        let base = pr.pull.base.branch;
+        let head_sha = pr.pull.head.sha;
+
+        let head_repo = pr
+            .pull
+            .head
+            .repo
+            .as_ref()
+            .map(|repo| repo.full_name.as_str());
+        let base_repo = pr
+            .pull
+            .base
+            .repo
+            .as_ref()
+            .map(|repo| repo.full_name.as_str());
+        let fork = match &pr.pull.head.repo {
+            Some(repo) if head_repo != base_repo => Some(repo.owner.login.clone()),
+            _ => None,
+        };
+
+        if opts.auto {
+            let checks = self
+                .checks(&client, &token, &owner, &owner_repo, &head_sha)
+                .await?;
+
+            if checks
+                .iter()
+                .any(|check| check.state == CheckState::Pending)
+            {
+                let method = match opts.strategy {
+                    Strategy::Merge => "MERGE",
+                    Strategy::Squash => "SQUASH",
+                    Strategy::Rebase => "REBASE",
+                };
+
+                let vars = graphql::AutoMerge {
+                    pull: &pr.pull.node_id,
+                    method,
+                    title: opts.title.as_deref(),
+                    body: opts.message.as_deref(),
+                    head: opts.head.as_deref(),
+                };
+
+                graphql::enable_auto_merge(&client, &self.base, &token, vars).await?;
+
+                return Ok(Merged {
+                    number,
+                    branch,
+                    base,
+                    head: head_sha,
+                    fork,
+                    sha: None,
+                });
+            }
+        }
+
+        let merge_method = match opts.strategy {
+            Strategy::Merge => "merge",
+            Strategy::Squash => "squash",
+            Strategy::Rebase => "rebase",
+        };
+
+        let payload = req::MergePull {
+            merge_method,
+            commit_title: opts.title.as_deref(),
+            commit_message: opts.message.as_deref(),
+            sha: opts.head.as_deref(),
+        };
+
+        let res = req::merge(
+            &client,
+            &self.base,
+            &owner,
+            &owner_repo,
+            &token,
+            number,
+            payload,
+        )
+        .await?;
+
+        // the branch of a fork belongs to its owner
+        if opts.delete_branch && fork.is_none() {
+            let deleted =
+                req::delete_branch(&client, &self.base, &owner, &owner_repo, &token, &branch).await;
+
+            if let Err(e) = deleted {
+                eprintln!("warning: failed to delete branch {branch}: {e}");
+            }
+        }
+
+        Ok(Merged {
+            number,
+            branch,
+            base,
+            head: head_sha,
+            fork,
+            sha: Some(res.sha),
+        })
+    }
+}
@@ -1,20 +1,41 @@
+use std::ffi::OsString;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::{DriverError, Runner};
 use crate::cred::find_token;
 use crate::driver::github::cli::GithubCli;
-use crate::driver::github::req::CreatePullRequest;
+use crate::driver::github::req::{
+    AddAssignees, AddLabels, CreatePullRequest, ListPulls, RequestReviewers, SetMilestone,
+    UpdatePullRequest,
+};
 use crate::errors::SysErrorHandler;
-use crate::tidal::Req;
+use crate::tidal::{
+    add_remote, Check, Conversation, Desc, Details, Head, ListFilter, MergeOptions, Merged,
+    PullNumber, PullRequest, Req, Review, Source, StateFilter, Summary, Transition,
+};
 use clap::Parser;
 
 pub use err::GithubError;
 use gix::remote::Direction;
+use gix::url::Scheme;
 use gix::Repository;
+use reqwest::Client;
 
 mod cli;
+mod comment;
 mod err;
+mod graphql;
+mod merge;
 mod req;
+mod review;
+mod stack;
+mod state;
+mod template;
+mod view;
+
+const PAGE_SIZE: usize = 100;
+const FORK_POLLS: usize = 15;
 
 /// Create a pull request with githubs [pull request api](https://docs.github.com/en/free-pro-team@latest/rest/pulls/pulls?apiVersion=2022-11-28#create-a-pull-request)
 pub struct Github {
@@ -23,23 +44,86 @@ pub struct Github {
 }
 
 impl Github {
-    async fn create_pull_request(self, repo: Repository, mut req: Req) -> Result<(), GithubError> {
-        let cli = GithubCli::parse();
-        req.overwrite(&cli.common);
-        let req = req.validate(&repo).handle_system_error();
+    async fn create_pull_request(
+        &self,
+        repo: Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), GithubError> {
+        let cli = GithubCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let mut req = req.validate(&repo).handle_system_error();
+
+        if cli.stack {
+            return self.create_stack(repo, req, cli).await;
+        }
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(GithubError::NoToken)?;
 
-        let id = get_id(&cli);
-        let body = get_body(&cli)?;
+        let mut forked = None;
+        if cli.fork {
+            let upstream = repo_parts(&repo, &req.to.remote)?;
+            let ssh = remote_scheme(&repo, &req.to.remote)? == Scheme::Ssh;
+
+            if let Some(fork) = self.fork(&client, &token, &upstream).await? {
+                let url = if ssh { &fork.ssh_url } else { &fork.clone_url };
+
+                // the remote is named after the owner of the fork
+                add_remote(&repo, &fork.owner.login, url).handle_system_error();
+                req.from.remote = fork.owner.login.clone();
+                forked = Some((fork.owner.login, fork.name));
+            }
+        }
+
+        if forked.is_some() {
+            req.push_branch(&repo, cli.common.force_with_lease)
+                .handle_system_error();
+        } else {
+            req.push(&repo, &cli.common).handle_system_error();
+        }
+
+        let summary = if cli.id.title.is_none() && cli.id.issue.is_none() {
+            Some(req.summary(&repo).handle_system_error())
+        } else {
+            None
+        };
+
+        let template = template::find(&repo, cli.template.as_deref())?;
+        let default_body = template
+            .as_deref()
+            .or(summary.as_ref().map(|s| s.body.as_str()));
+
+        let (id, body) = if cli.description == Some(Desc::Editor) && cli.id.issue.is_none() {
+            compose(&repo, &req, &cli, summary.as_ref(), default_body)
+        } else {
+            (
+                get_id(&cli, summary.as_ref()),
+                get_body(&cli, default_body)?,
+            )
+        };
+
+        // the remote of a fork created just now isn't in the config snapshot of `repo`
+        let remotes = match forked {
+            Some(fork) => Remotes {
+                upstream: repo_parts(&repo, &req.to.remote)?,
+                fork,
+            },
+            None => Remotes::of(&repo, &req)?,
+        };
+        let (owner, owner_repo) = self
+            .upstream(&client, &token, &remotes, cli.in_fork)
+            .await?;
 
         let draft = cli.draft;
         let maintainer_can_modify = cli.fixup;
-        let head_repo = None;
 
-        let (our, _) = repo_parts(&repo, &req.to.remote)?;
-        let (owner, owner_repo) = repo_parts(&repo, &req.from.remote)?;
+        // a fork in the same organization can only be told apart by its name
+        let (fork_owner, fork_repo) = remotes.fork;
+        let head_repo = (fork_owner == owner && fork_repo != owner_repo).then_some(fork_repo);
 
         let base = req.to.branch;
-        let head = format!("{}:{}", our, req.from.branch);
+        let head = format!("{}:{}", fork_owner, req.from.branch);
 
         let payload = CreatePullRequest {
             id,
@@ -51,10 +135,68 @@ impl Github {
             maintainer_can_modify,
         };
 
-        let client = reqwest::Client::new();
-        let token = find_token(&self.base).map_err(|_| GithubError::NoToken)?;
-        let res =
-            req::send_request(&client, &self.base, &owner, &owner_repo, &token, payload).await?;
+        // by head only, so --update can retarget a pull request to another base
+        let query = ListPulls {
+            state: "open",
+            head: Some(&payload.head),
+            base: None,
+            per_page: 1,
+            page: 1,
+        };
+        let existing = req::list_pulls(&client, &self.base, &owner, &owner_repo, &token, &query)
+            .await?
+            .into_iter()
+            .next();
+
+        let res = match existing {
+            Some(existing) if cli.update => {
+                // only what was given explicitly, so edits made on github survive
+                let composed = cli.description == Some(Desc::Editor);
+                let title = match payload.id {
+                    req::Id::Title(title) if cli.id.title.is_some() || composed => Some(title),
+                    _ => None,
+                };
+                let explicit_body = cli.description.is_some() || cli.template.is_some();
+
+                let update = UpdatePullRequest {
+                    title,
+                    body: explicit_body.then_some(payload.body),
+                    base: cli.common.refs.to_branch.is_some().then_some(payload.base),
+                };
+
+                let res = req::update_pull(
+                    &client,
+                    &self.base,
+                    &owner,
+                    &owner_repo,
+                    &token,
+                    existing.number,
+                    update,
+                )
+                .await?;
+
+                if payload.draft && !existing.draft {
+                    graphql::convert_to_draft(&client, &self.base, &token, &res.node_id).await?;
+                } else if cli.ready && existing.draft {
+                    graphql::mark_ready(&client, &self.base, &token, &res.node_id).await?;
+                }
+
+                res
+            }
+
+            Some(existing) => {
+                println!("{}", existing.html_url);
+                return Err(GithubError::Exists(existing.number));
+            }
+
+            None => {
+                req::send_request(&client, &self.base, &owner, &owner_repo, &token, payload).await?
+            }
+        };
+
+        let meta = Metadata::new(&repo, &cli);
+        self.decorate(&client, &token, &(owner, owner_repo), &res, &meta)
+            .await?;
 
         if cli.open {
             open::that(res.url).map_err(|_| GithubError::FailedToOpen)?;
@@ -62,38 +204,438 @@ impl Github {
 
         Ok(())
     }
+
+    async fn list_pull_requests(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, GithubError> {
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(GithubError::NoToken)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+
+        let state = match filter.state {
+            StateFilter::Open => "open",
+            StateFilter::Closed | StateFilter::Merged => "closed",
+            StateFilter::All => "all",
+        };
+
+        let head = filter.head.as_ref().map(|head| {
+            if head.contains(':') {
+                head.to_string()
+            } else {
+                format!("{head_owner}:{head}")
+            }
+        });
+
+        let mut prs = Vec::new();
+        for page in 1.. {
+            let query = ListPulls {
+                state,
+                head: head.as_deref(),
+                base: filter.base.as_deref(),
+                per_page: PAGE_SIZE,
+                page,
+            };
+
+            let batch =
+                req::list_pulls(&client, &self.base, &owner, &owner_repo, &token, &query).await?;
+            let last = batch.len() < PAGE_SIZE;
+
+            prs.extend(
+                batch
+                    .into_iter()
+                    .map(PullRequest::from)
+                    .filter(|pr| filter.matches(pr)),
+            );
+
+            if last || prs.len() >= filter.limit {
+                break;
+            }
+        }
+
+        prs.truncate(filter.limit);
+        Ok(prs)
+    }
+
+    async fn pull_head(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: PullNumber,
+    ) -> Result<Head, GithubError> {
+        let ssh = remote_scheme(&repo, &req.to.remote)? == Scheme::Ssh;
+
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let number = in_repo(&number, &owner, &owner_repo)?;
+
+        // the to remote is a fork of the repository of the pull request
+        let remote_is_base = remotes.upstream == (owner.clone(), owner_repo.clone());
+
+        let pr = req::get_pull(&client, &self.base, &owner, &owner_repo, &token, number).await?;
+        let head = pr.pull.head;
+
+        let same_repo = match (&head.repo, &pr.pull.base.repo) {
+            (Some(head), Some(base)) => head.full_name == base.full_name,
+            _ => false,
+        };
+
+        let source = match head.repo {
+            Some(_) if same_repo && remote_is_base => Source::Target,
+            Some(fork) => Source::Fork {
+                owner: fork.owner.login,
+                url: if ssh { fork.ssh_url } else { fork.clone_url },
+            },
+            None => Source::Deleted,
+        };
+
+        Ok(Head {
+            number,
+            branch: head.branch,
+            source,
+            pull_ref: Some(format!("refs/pull/{number}/head")),
+        })
+    }
+
+    /// Owner and name of the repository pull requests are opened against, the upstream
+    /// of `remotes` unless it's the fork itself and was forked from another repository.
+    ///
+    /// Every subcommand resolves the repository this way, so they find the pull requests
+    /// create opened.
+    async fn upstream(
+        &self,
+        client: &Client,
+        token: &str,
+        remotes: &Remotes,
+        in_fork: bool,
+    ) -> Result<(String, String), GithubError> {
+        let Remotes { upstream, fork } = remotes;
+        if in_fork || upstream != fork {
+            return Ok(upstream.clone());
+        }
+
+        let (owner, name) = upstream;
+        let info = req::get_repo(client, &self.base, owner, name, token).await?;
+
+        match info.parent {
+            Some(parent) => Ok((parent.owner.login, parent.name)),
+            None => Ok(upstream.clone()),
+        }
+    }
+
+    /// Fork `upstream` unless the user can push to it
+    async fn fork(
+        &self,
+        client: &Client,
+        token: &str,
+        upstream: &(String, String),
+    ) -> Result<Option<req::Fork>, GithubError> {
+        let (owner, name) = upstream;
+
+        let info = req::get_repo(client, &self.base, owner, name, token).await?;
+        if info.permissions.is_some_and(|permissions| permissions.push) {
+            return Ok(None);
+        }
+
+        let fork = req::create_fork(client, &self.base, owner, name, token).await?;
+
+        // forks are created in the background
+        for _ in 0..FORK_POLLS {
+            let created =
+                req::get_repo(client, &self.base, &fork.owner.login, &fork.name, token).await;
+            if created.is_ok() {
+                return Ok(Some(fork));
+            }
+
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+
+        Err(GithubError::ForkTimeout(format!(
+            "{}/{}",
+            fork.owner.login, fork.name
+        )))
+    }
+
+    /// Request reviews and add the assignees, labels, milestone and project of `meta`
+    /// to the pull request `pr` of `upstream`
+    async fn decorate(
+        &self,
+        client: &Client,
+        token: &str,
+        upstream: &(String, String),
+        pr: &req::CreatePullRequestResponse,
+        meta: &Metadata,
+    ) -> Result<(), GithubError> {
+        let (owner, owner_repo) = upstream;
+
+        if !meta.reviewers.is_empty() || !meta.team_reviewers.is_empty() {
+            let payload = RequestReviewers {
+                reviewers: meta.reviewers.clone(),
+                team_reviewers: meta.team_reviewers.clone(),
+            };
+
+            req::request_reviewers(
+                client, &self.base, owner, owner_repo, token, pr.number, payload,
+            )
+            .await?;
+        }
+
+        if !meta.assignees.is_empty() {
+            let payload = AddAssignees {
+                assignees: meta.assignees.clone(),
+            };
+
+            req::add_assignees(
+                client, &self.base, owner, owner_repo, token, pr.number, payload,
+            )
+            .await?;
+        }
+
+        if !meta.labels.is_empty() {
+            let payload = AddLabels {
+                labels: meta.labels.clone(),
+            };
+
+            req::add_labels(
+                client, &self.base, owner, owner_repo, token, pr.number, payload,
+            )
+            .await?;
+        }
+
+        if let Some(title) = &meta.milestone {
+            let milestone =
+                req::find_milestone(client, &self.base, owner, owner_repo, token, title).await?;
+
+            let payload = SetMilestone {
+                milestone: milestone.number,
+            };
+
+            req::set_milestone(
+                client, &self.base, owner, owner_repo, token, pr.number, payload,
+            )
+            .await?;
+        }
+
+        if let Some(project) = &meta.project {
+            let (project_owner, number) = project_ref(project, owner)?;
+
+            graphql::add_to_project(
+                client,
+                &self.base,
+                token,
+                &project_owner,
+                number,
+                &pr.node_id,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    fn token(&self, repo: &Repository) -> Result<String, GithubError> {
+        find_token(repo, &self.base).map_err(GithubError::NoToken)
+    }
+
+    /// The pull request `number`, or the open pull request from `head` (`owner:branch`)
+    async fn pull_number(
+        &self,
+        client: &Client,
+        token: &str,
+        owner: &str,
+        owner_repo: &str,
+        head: &str,
+        number: Option<PullNumber>,
+    ) -> Result<u64, GithubError> {
+        if let Some(number) = number {
+            return in_repo(&number, owner, owner_repo);
+        }
+
+        let query = ListPulls {
+            state: "open",
+            head: Some(head),
+            base: None,
+            per_page: 1,
+            page: 1,
+        };
+
+        let pulls = req::list_pulls(client, &self.base, owner, owner_repo, token, &query).await?;
+
+        pulls
+            .into_iter()
+            .next()
+            .map(|pr| pr.number)
+            .ok_or_else(|| GithubError::NoPullRequest(head.to_string()))
+    }
+}
+
+fn compose(
+    repo: &Repository,
+    req: &Req,
+    cli: &GithubCli,
+    summary: Option<&Summary>,
+    body: Option<&str>,
+) -> (req::Id, String) {
+    let title = cli
+        .id
+        .title
+        .as_deref()
+        .or(summary.map(|s| s.title.as_str()))
+        .unwrap_or_default();
+    let body = body.unwrap_or_default();
+
+    let composed = req.compose(repo, title, body).handle_system_error();
+
+    (req::Id::Title(composed.title), composed.body)
 }
 
-fn get_body(cli: &GithubCli) -> Result<String, GithubError> {
+fn get_body(cli: &GithubCli, default: Option<&str>) -> Result<String, GithubError> {
     let body = if let Some(desc) = &cli.description {
         match desc.read() {
             Ok(s) => s,
             Err(_) => return Err(GithubError::FailedDescription(desc.clone()).into()),
         }
+    } else if let Some(default) = default {
+        default.to_string()
     } else {
         "".to_string()
     };
     Ok(body)
 }
 
-fn get_id(cli: &GithubCli) -> req::Id {
+fn get_id(cli: &GithubCli, summary: Option<&Summary>) -> req::Id {
     let id = if let Some(title) = &cli.id.title {
         req::Id::Title(title.to_string())
     } else if let Some(issue) = &cli.id.issue {
         req::Id::Issue(*issue)
+    } else if let Some(summary) = summary {
+        req::Id::Title(summary.title.clone())
     } else {
         unreachable!()
     };
     id
 }
 
+/// Owner and name of the repositories of the remotes of a request
+struct Remotes {
+    /// Of the to remote
+    upstream: (String, String),
+    /// Of the from remote
+    fork: (String, String),
+}
+
+impl Remotes {
+    fn of(repo: &Repository, req: &Req) -> Result<Self, GithubError> {
+        Ok(Remotes {
+            upstream: repo_parts(repo, &req.to.remote)?,
+            fork: repo_parts(repo, &req.from.remote)?,
+        })
+    }
+}
+
+/// Reviewers, assignees, labels, milestone and project of new pull requests,
+/// given on the command line or configured
+struct Metadata {
+    reviewers: Vec<String>,
+    team_reviewers: Vec<String>,
+    assignees: Vec<String>,
+    labels: Vec<String>,
+    milestone: Option<String>,
+    project: Option<String>,
+}
+
+impl Metadata {
+    fn new(repo: &Repository, cli: &GithubCli) -> Self {
+        Metadata {
+            reviewers: config_list(repo, "reviewers", &cli.reviewers),
+            team_reviewers: cli.team_reviewers.clone(),
+            assignees: cli.assignees.clone(),
+            labels: config_list(repo, "labels", &cli.labels),
+            milestone: config_value(repo, "milestone", cli.milestone.as_deref()),
+            project: config_value(repo, "project", cli.project.as_deref()),
+        }
+    }
+}
+
+/// Values given on the command line, falling back to `tidal.<key>`
+/// which may be repeated or comma separated
+fn config_list(repo: &Repository, key: &str, given: &[String]) -> Vec<String> {
+    if !given.is_empty() {
+        return given.to_vec();
+    }
+
+    repo.config_snapshot()
+        .strings("tidal", None, key)
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|value| {
+            value
+                .to_string()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Value given on the command line, falling back to `tidal.<key>`
+fn config_value(repo: &Repository, key: &str, given: Option<&str>) -> Option<String> {
+    if let Some(given) = given {
+        return Some(given.to_string());
+    }
+
+    let snapshot = repo.config_snapshot();
+    let value = snapshot.string(format!("tidal.{key}").as_str());
+
+    value.map(|value| value.to_string())
+}
+
+/// The number of a pull request, refusing urls of another repository
+fn in_repo(number: &PullNumber, owner: &str, owner_repo: &str) -> Result<u64, GithubError> {
+    let path = format!("{owner}/{owner_repo}");
+
+    match &number.repo {
+        Some(repo) if !number.belongs_to(&path) => {
+            Err(GithubError::OtherRepository(repo.clone(), path))
+        }
+        _ => Ok(number.number),
+    }
+}
+
+/// Split a `[owner/]number` project reference, defaulting to the repository owner
+fn project_ref(project: &str, owner: &str) -> Result<(String, u64), GithubError> {
+    let (project_owner, number) = project.rsplit_once('/').unwrap_or((owner, project));
+
+    match number.parse() {
+        Ok(number) => Ok((project_owner.to_string(), number)),
+        Err(_) => Err(GithubError::NoProject(project.to_string())),
+    }
+}
+
+fn remote_scheme(repo: &Repository, remote: &str) -> Result<Scheme, GithubError> {
+    repo.find_remote(remote)
+        .ok()
+        .and_then(|found| found.url(Direction::Fetch).map(|url| url.scheme.clone()))
+        .ok_or_else(|| GithubError::NoRemoteUrl(remote.to_string()))
+}
+
 fn repo_parts(repo: &Repository, remote: &str) -> Result<(String, String), GithubError> {
     let remote = repo.find_remote(remote).unwrap();
     let url = remote.url(Direction::Push).unwrap();
 
     let mut path = PathBuf::from(url.path.to_string());
 
-    let repo = path.file_name().unwrap().to_string_lossy().to_string();
+    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    let repo = name.strip_suffix(".git").unwrap_or(&name).to_string();
     path.pop();
     let owner = path.file_name().unwrap().to_string_lossy().to_string();
 
@@ -102,11 +644,109 @@ fn repo_parts(repo: &Repository, remote: &str) -> Result<(String, String), Githu
 
 #[async_trait::async_trait]
 impl Runner for Github {
-    async fn run(self, repo: Repository, req: Req) -> Result<(), DriverError> {
-        self.create_pull_request(repo, req)
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.create_pull_request(repo, req, args)
             .await
             .map_err(|e| DriverError::Github(e))?;
 
         Ok(())
     }
+
+    async fn list(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, DriverError> {
+        Ok(self.list_pull_requests(repo, req, filter).await?)
+    }
+
+    async fn view(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Details, DriverError> {
+        Ok(self.view_pull_request(repo, req, number).await?)
+    }
+
+    async fn checks(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Vec<Check>, DriverError> {
+        Ok(self.pull_request_checks(repo, req, number).await?)
+    }
+
+    async fn head(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: PullNumber,
+    ) -> Result<Head, DriverError> {
+        Ok(self.pull_head(repo, req, number).await?)
+    }
+
+    async fn merge(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        opts: MergeOptions,
+    ) -> Result<Merged, DriverError> {
+        Ok(self.merge_pull_request(repo, req, number, opts).await?)
+    }
+
+    async fn restack(&self, repo: Repository, req: Req) -> Result<(), DriverError> {
+        Ok(self.restack_pull_requests(repo, req).await?)
+    }
+
+    async fn transition(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        transition: Transition,
+    ) -> Result<u64, DriverError> {
+        Ok(self
+            .transition_pull_request(repo, req, number, transition)
+            .await?)
+    }
+
+    async fn review(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        review: Review,
+    ) -> Result<u64, DriverError> {
+        Ok(self.review_pull_request(repo, req, number, review).await?)
+    }
+
+    async fn comment(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        body: String,
+    ) -> Result<String, DriverError> {
+        Ok(self
+            .comment_on_pull_request(repo, req, number, body)
+            .await?)
+    }
+
+    async fn comments(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Conversation, DriverError> {
+        Ok(self.pull_request_conversation(repo, req, number).await?)
+    }
 }
@@ -1,14 +1,17 @@
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
 
-use super::GithubError;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{GithubError, PAGE_SIZE};
+use crate::tidal::{CheckState, State};
 
 #[derive(Serialize, Debug)]
 pub struct CreatePullRequest {
     #[serde(flatten)]
     pub id: Id,
     pub head: String,
-    #[serde(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub head_repo: Option<String>,
     pub base: String,
     pub body: String,
@@ -28,6 +31,367 @@ pub enum Id {
 #[derive(Deserialize)]
 pub struct CreatePullRequestResponse {
     pub url: String,
+    pub number: u64,
+    pub node_id: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UpdatePullRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SetState {
+    pub state: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AddComment {
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+pub struct AddCommentResponse {
+    pub html_url: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SubmitReview {
+    pub event: &'static str,
+    pub body: String,
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReviewComment {
+    pub path: String,
+    pub line: u64,
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+pub struct Repo {
+    /// The repository this one was forked from
+    pub parent: Option<ParentRepo>,
+    /// Permissions of the authenticated user
+    pub permissions: Option<Permissions>,
+}
+
+#[derive(Deserialize)]
+pub struct Permissions {
+    pub push: bool,
+}
+
+#[derive(Deserialize)]
+pub struct Fork {
+    pub name: String,
+    pub owner: User,
+    pub clone_url: String,
+    pub ssh_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParentRepo {
+    pub name: String,
+    pub owner: User,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RequestReviewers {
+    pub reviewers: Vec<String>,
+    pub team_reviewers: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AddAssignees {
+    pub assignees: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AddLabels {
+    pub labels: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SetMilestone {
+    pub milestone: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ListPulls<'a> {
+    pub state: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<&'a str>,
+    pub per_page: usize,
+    pub page: usize,
+}
+
+#[derive(Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub node_id: String,
+    pub title: String,
+    pub user: User,
+    pub head: Branch,
+    pub base: Branch,
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub merged_at: Option<String>,
+    pub labels: Vec<Label>,
+    pub html_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Deserialize)]
+pub struct Branch {
+    #[serde(rename = "ref")]
+    pub branch: String,
+    pub sha: String,
+    /// Missing when the repository was deleted
+    pub repo: Option<HeadRepo>,
+}
+
+#[derive(Deserialize)]
+pub struct HeadRepo {
+    pub full_name: String,
+    pub owner: User,
+    pub clone_url: String,
+    pub ssh_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+impl From<PullRequest> for crate::tidal::PullRequest {
+    fn from(pr: PullRequest) -> Self {
+        let state = match (pr.state.as_str(), pr.merged_at) {
+            ("open", _) => State::Open,
+            (_, Some(_)) => State::Merged,
+            _ => State::Closed,
+        };
+
+        crate::tidal::PullRequest {
+            number: pr.number,
+            title: pr.title,
+            author: pr.user.login,
+            head: pr.head.branch,
+            base: pr.base.branch,
+            state,
+            draft: pr.draft,
+            labels: pr.labels.into_iter().map(|l| l.name).collect(),
+            url: pr.html_url,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PullRequestDetails {
+    #[serde(flatten)]
+    pub pull: PullRequest,
+    pub body: Option<String>,
+    pub mergeable: Option<bool>,
+    pub mergeable_state: Option<String>,
+    #[serde(default)]
+    pub requested_reviewers: Vec<User>,
+    #[serde(default)]
+    pub requested_teams: Vec<Team>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MergePull<'a> {
+    pub merge_method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_title: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+pub struct MergePullResponse {
+    pub sha: String,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    message: String,
+}
+
+#[derive(Deserialize)]
+pub struct Team {
+    pub slug: String,
+}
+
+#[derive(Deserialize)]
+pub struct Review {
+    pub user: Option<User>,
+    pub state: String,
+}
+
+#[derive(Deserialize)]
+pub struct CheckRuns {
+    pub check_runs: Vec<CheckRun>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CombinedStatus {
+    pub statuses: Vec<Status>,
+}
+
+#[derive(Deserialize)]
+pub struct Status {
+    pub context: String,
+    pub state: String,
+    pub target_url: Option<String>,
+}
+
+impl From<CheckRun> for crate::tidal::Check {
+    fn from(run: CheckRun) -> Self {
+        let state = match (run.status.as_str(), run.conclusion.as_deref()) {
+            ("completed", Some("success")) => CheckState::Success,
+            ("completed", Some("cancelled")) => CheckState::Cancelled,
+            ("completed", Some("skipped")) => CheckState::Skipped,
+            ("completed", Some("neutral" | "stale")) => CheckState::Neutral,
+            ("completed", _) => CheckState::Failure,
+            _ => CheckState::Pending,
+        };
+
+        let started = run
+            .started_at
+            .and_then(|at| humantime::parse_rfc3339(&at).ok());
+        let completed = match run.completed_at {
+            Some(at) => humantime::parse_rfc3339(&at).ok(),
+            None => Some(SystemTime::now()),
+        };
+
+        let duration = match (started, completed) {
+            (Some(started), Some(completed)) => completed
+                .duration_since(started)
+                .ok()
+                .map(|duration| duration.as_secs()),
+            _ => None,
+        };
+
+        crate::tidal::Check {
+            name: run.name,
+            state,
+            url: run.html_url,
+            duration,
+        }
+    }
+}
+
+impl From<Status> for crate::tidal::Check {
+    fn from(status: Status) -> Self {
+        let state = match status.state.as_str() {
+            "success" => CheckState::Success,
+            "pending" => CheckState::Pending,
+            _ => CheckState::Failure,
+        };
+
+        crate::tidal::Check {
+            name: status.context,
+            state,
+            url: status.target_url,
+            duration: None,
+        }
+    }
+}
+
+pub(super) fn github(req: RequestBuilder, token: &str) -> RequestBuilder {
+    req.header("User-Agent", "Tidal")
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .header("Authorization", format!("Bearer {}", token))
+}
+
+async fn post<T: Serialize>(
+    client: &Client,
+    url: String,
+    token: &str,
+    body: &T,
+) -> Result<Response, GithubError> {
+    let req = github(client.post(url), token).json(body);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    Ok(res)
+}
+
+async fn get<T: DeserializeOwned, Q: Serialize + ?Sized>(
+    client: &Client,
+    url: String,
+    token: &str,
+    query: &Q,
+) -> Result<T, GithubError> {
+    let res = github(client.get(url), token).query(query).send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    Ok(res.json().await?)
+}
+
+pub async fn get_repo(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+) -> Result<Repo, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}");
+
+    get(client, url, token, &()).await
+}
+
+pub async fn create_fork(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+) -> Result<Fork, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/forks");
+
+    let res = post(client, url, token, &serde_json::json!({})).await?;
+
+    Ok(res.json().await?)
 }
 
 pub async fn send_request(
@@ -40,21 +404,372 @@ pub async fn send_request(
 ) -> Result<CreatePullRequestResponse, GithubError> {
     let url = format!("https://{base}/repos/{owner}/{repo}/pulls");
 
-    let req = client
-        .post(url)
-        .header("User-Agent", "Tidal")
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("Authorization", format!("Bearer {}", token))
-        .json(&req);
+    let res = post(client, url, token, &req).await?;
+    let res: CreatePullRequestResponse = res.json().await?;
 
-    let res = req.send().await?;
+    Ok(res)
+}
+
+pub async fn update_pull(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: UpdatePullRequest,
+) -> Result<CreatePullRequestResponse, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}");
+
+    let res = github(client.patch(url), token).json(&req).send().await?;
     if let Err(e) = res.error_for_status_ref() {
         log::error!("failed request: {}", res.text().await.unwrap());
         return Err(GithubError::Http(e));
     }
 
-    let res: CreatePullRequestResponse = res.json().await?;
+    Ok(res.json().await?)
+}
 
-    Ok(res)
+pub async fn set_state(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: SetState,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}");
+
+    let res = github(client.patch(url), token).json(&req).send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    Ok(())
+}
+
+pub async fn add_comment(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: AddComment,
+) -> Result<AddCommentResponse, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/issues/{number}/comments");
+
+    let res = post(client, url, token, &req).await?;
+
+    Ok(res.json().await?)
+}
+
+pub async fn submit_review(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: SubmitReview,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}/reviews");
+
+    post(client, url, token, &req).await?;
+
+    Ok(())
+}
+
+pub async fn request_reviewers(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: RequestReviewers,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}/requested_reviewers");
+
+    post(client, url, token, &req).await?;
+
+    Ok(())
+}
+
+pub async fn add_assignees(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: AddAssignees,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/issues/{number}/assignees");
+
+    post(client, url, token, &req).await?;
+
+    Ok(())
+}
+
+pub async fn add_labels(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: AddLabels,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/issues/{number}/labels");
+
+    post(client, url, token, &req).await?;
+
+    Ok(())
+}
+
+pub async fn find_milestone(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    title: &str,
+) -> Result<Milestone, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/milestones");
+
+    for page in 1.. {
+        let query = [
+            ("state", "open".to_string()),
+            ("per_page", PAGE_SIZE.to_string()),
+            ("page", page.to_string()),
+        ];
+
+        let milestones: Vec<Milestone> = get(client, url.clone(), token, &query).await?;
+        let last = milestones.len() < PAGE_SIZE;
+
+        if let Some(milestone) = milestones.into_iter().find(|m| m.title == title) {
+            return Ok(milestone);
+        }
+
+        if last {
+            break;
+        }
+    }
+
+    Err(GithubError::NoMilestone(title.to_string()))
+}
+
+pub async fn set_milestone(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: SetMilestone,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/issues/{number}");
+
+    let res = github(client.patch(url), token).json(&req).send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    Ok(())
+}
+
+pub async fn list_pulls(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    query: &ListPulls<'_>,
+) -> Result<Vec<PullRequest>, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls");
+
+    get(client, url, token, query).await
+}
+
+pub async fn get_pull(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+) -> Result<PullRequestDetails, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}");
+
+    get(client, url, token, &()).await
+}
+
+pub async fn list_reviews(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+) -> Result<Vec<Review>, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}/reviews");
+
+    get(client, url, token, &[("per_page", "100")]).await
+}
+
+pub async fn check_runs(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    sha: &str,
+) -> Result<CheckRuns, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/commits/{sha}/check-runs");
+
+    get(client, url, token, &[("per_page", "100")]).await
+}
+
+pub async fn combined_status(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    sha: &str,
+) -> Result<CombinedStatus, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/commits/{sha}/status");
+
+    get(client, url, token, &()).await
+}
+
+pub async fn merge(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    req: MergePull<'_>,
+) -> Result<MergePullResponse, GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/pulls/{number}/merge");
+
+    let res = github(client.put(url), token).json(&req).send().await?;
+    match res.status().as_u16() {
+        405 => {
+            let msg: Message = res.json().await?;
+            return Err(GithubError::NotMergeable(msg.message));
+        }
+        409 => return Err(GithubError::HeadChanged),
+        _ => (),
+    }
+
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    Ok(res.json().await?)
+}
+
+pub async fn delete_branch(
+    client: &Client,
+    base: &str,
+    owner: &str,
+    repo: &str,
+    token: &str,
+    branch: &str,
+) -> Result<(), GithubError> {
+    let url = format!("https://{base}/repos/{owner}/{repo}/git/refs/heads/{branch}");
+
+    let res = github(client.delete(url), token).send().await?;
+
+    // the repository may already delete merged branches on its own
+    if res.status().as_u16() == 422 {
+        return Ok(());
+    }
+
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(GithubError::Http(e));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PullRequest, PullRequestDetails};
+
+    /// A pull request as returned by `GET /repos/{owner}/{repo}/pulls/{number}`, trimmed
+    const PULL: &str = r#"{
+        "url": "https://api.github.com/repos/octocat/hello/pulls/1347",
+        "id": 1,
+        "node_id": "MDExOlB1bGxSZXF1ZXN0MQ==",
+        "html_url": "https://github.com/octocat/hello/pull/1347",
+        "number": 1347,
+        "state": "open",
+        "locked": false,
+        "title": "Amazing new feature",
+        "user": { "login": "octocat", "id": 1 },
+        "body": "Please pull these awesome changes in!",
+        "labels": [{ "id": 208045946, "name": "bug", "color": "f29513" }],
+        "created_at": "2011-01-26T19:01:12Z",
+        "merged_at": null,
+        "requested_reviewers": [{ "login": "other_user", "id": 2 }],
+        "requested_teams": [{ "id": 1, "slug": "justice-league" }],
+        "head": {
+            "label": "octocat:new-topic",
+            "ref": "new-topic",
+            "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+            "user": { "login": "octocat", "id": 1 },
+            "repo": {
+                "id": 1296269,
+                "name": "hello",
+                "full_name": "octocat/hello",
+                "owner": { "login": "octocat", "id": 1 },
+                "clone_url": "https://github.com/octocat/hello.git",
+                "ssh_url": "git@github.com:octocat/hello.git"
+            }
+        },
+        "base": {
+            "label": "octocat:master",
+            "ref": "master",
+            "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+            "user": { "login": "octocat", "id": 1 },
+            "repo": null
+        },
+        "draft": false,
+        "merged": false,
+        "mergeable": true,
+        "mergeable_state": "clean"
+    }"#;
+
+    #[test]
+    fn pull_details() {
+        let pr: PullRequestDetails = serde_json::from_str(PULL).unwrap();
+
+        assert_eq!(pr.pull.number, 1347);
+        assert_eq!(pr.pull.node_id, "MDExOlB1bGxSZXF1ZXN0MQ==");
+        assert_eq!(pr.pull.head.branch, "new-topic");
+        assert_eq!(
+            pr.body.as_deref(),
+            Some("Please pull these awesome changes in!")
+        );
+        assert_eq!(pr.mergeable, Some(true));
+        assert_eq!(pr.requested_reviewers[0].login, "other_user");
+        assert_eq!(pr.requested_teams[0].slug, "justice-league");
+    }
+
+    #[test]
+    fn listed_pull() {
+        let prs: Vec<PullRequest> = serde_json::from_str(&format!("[{PULL}]")).unwrap();
+
+        assert_eq!(prs[0].node_id, "MDExOlB1bGxSZXF1ZXN0MQ==");
+        assert!(prs[0].base.repo.is_none());
+    }
 }
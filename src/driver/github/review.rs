@@ -0,0 +1,62 @@
+use gix::Repository;
+use reqwest::Client;
+
+use super::{req, Github, GithubError, Remotes};
+use crate::tidal::{PullNumber, Req, Review, Verdict};
+
+impl Github {
+    pub(super) async fn review_pull_request(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        review: Review,
+    ) -> Result<u64, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        let number = self
+            .pull_number(&client, &token, &owner, &owner_repo, &head, number)
+            .await?;
+
+        let event = match review.verdict {
+            Verdict::Approve => "APPROVE",
+            Verdict::RequestChanges => "REQUEST_CHANGES",
+            Verdict::Comment => "COMMENT",
+        };
+
+        let comments = review
+            .comments
+            .into_iter()
+            .map(|comment| req::ReviewComment {
+                path: comment.path,
+                line: comment.line,
+                body: comment.body,
+            })
+            .collect();
+
+        let payload = req::SubmitReview {
+            event,
+            body: review.body,
+            comments,
+        };
+
+        req::submit_review(
+            &client,
+            &self.base,
+            &owner,
+            &owner_repo,
+            &token,
+            number,
+            payload,
+        )
+        .await?;
+
+        Ok(number)
+    }
+}
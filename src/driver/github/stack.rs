@@ -0,0 +1,210 @@
+use gix::Repository;
+use reqwest::Client;
+
+use super::{cli::GithubCli, req, Github, GithubError, Metadata, Remotes};
+use crate::errors::SysErrorHandler;
+use crate::tidal::{with_navigation, Req};
+
+impl Github {
+    /// Create or retarget a pull request for every branch of the stack of the from branch,
+    /// each based on the branch below it
+    pub(super) async fn create_stack(
+        &self,
+        repo: Repository,
+        req: Req,
+        cli: GithubCli,
+    ) -> Result<(), GithubError> {
+        let stack = req.stack(&repo).handle_system_error();
+
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self
+            .upstream(&client, &token, &remotes, cli.in_fork)
+            .await?;
+        let (head_owner, head_name) = remotes.fork;
+        let head_repo = (head_owner == owner && head_name != owner_repo).then_some(head_name);
+
+        let meta = Metadata::new(&repo, &cli);
+        let mut numbers = Vec::new();
+        let mut base = stack.base.clone();
+
+        for branch in &stack.branches {
+            let layer = req.layer(branch, &base);
+            layer.push(&repo, &cli.common).handle_system_error();
+
+            let head = format!("{head_owner}:{branch}");
+            let existing = self
+                .latest_pull(&client, &token, &owner, &owner_repo, &head, "open")
+                .await?;
+
+            let pr = match existing {
+                Some(pr) => {
+                    if pr.base.branch != base {
+                        self.retarget(&client, &token, &owner, &owner_repo, pr.number, &base)
+                            .await?;
+                    }
+
+                    req::CreatePullRequestResponse {
+                        url: pr.html_url,
+                        number: pr.number,
+                        node_id: pr.node_id,
+                    }
+                }
+
+                None => {
+                    let summary = layer.summary(&repo).handle_system_error();
+                    let payload = req::CreatePullRequest {
+                        id: req::Id::Title(summary.title),
+                        head,
+                        head_repo: head_repo.clone(),
+                        base: base.clone(),
+                        body: summary.body,
+                        draft: cli.draft,
+                        maintainer_can_modify: cli.fixup,
+                    };
+
+                    req::send_request(&client, &self.base, &owner, &owner_repo, &token, payload)
+                        .await?
+                }
+            };
+
+            let upstream = (owner.clone(), owner_repo.clone());
+            self.decorate(&client, &token, &upstream, &pr, &meta)
+                .await?;
+
+            eprintln!("#{} {branch} -> {base}", pr.number);
+            numbers.push(pr.number);
+            base = branch.clone();
+        }
+
+        self.navigate(&client, &token, &owner, &owner_repo, &stack.base, &numbers)
+            .await
+    }
+
+    /// Point the open pull requests of the stack of the from branch at the branch
+    /// below them, skipping branches whose pull request was merged or closed
+    pub(super) async fn restack_pull_requests(
+        &self,
+        repo: Repository,
+        req: Req,
+    ) -> Result<(), GithubError> {
+        let stack = req.stack(&repo).handle_system_error();
+
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+
+        let mut numbers = Vec::new();
+        let mut base = stack.base.clone();
+
+        for branch in &stack.branches {
+            let head = format!("{head_owner}:{branch}");
+            let latest = self
+                .latest_pull(&client, &token, &owner, &owner_repo, &head, "all")
+                .await?;
+
+            match latest {
+                Some(pr) if pr.state == "open" => {
+                    if pr.base.branch != base {
+                        self.retarget(&client, &token, &owner, &owner_repo, pr.number, &base)
+                            .await?;
+                        eprintln!("#{} {branch} -> {base}", pr.number);
+                    }
+
+                    numbers.push(pr.number);
+                    base = branch.clone();
+                }
+
+                // the branches above take its place
+                Some(_) => (),
+
+                None => base = branch.clone(),
+            }
+        }
+
+        self.navigate(&client, &token, &owner, &owner_repo, &stack.base, &numbers)
+            .await
+    }
+
+    /// The most recent pull request from `head` in `state`
+    async fn latest_pull(
+        &self,
+        client: &Client,
+        token: &str,
+        owner: &str,
+        owner_repo: &str,
+        head: &str,
+        state: &str,
+    ) -> Result<Option<req::PullRequest>, GithubError> {
+        let query = req::ListPulls {
+            state,
+            head: Some(head),
+            base: None,
+            per_page: 1,
+            page: 1,
+        };
+
+        let pulls = req::list_pulls(client, &self.base, owner, owner_repo, token, &query).await?;
+
+        Ok(pulls.into_iter().next())
+    }
+
+    async fn retarget(
+        &self,
+        client: &Client,
+        token: &str,
+        owner: &str,
+        owner_repo: &str,
+        number: u64,
+        base: &str,
+    ) -> Result<(), GithubError> {
+        let update = req::UpdatePullRequest {
+            title: None,
+            body: None,
+            base: Some(base.to_string()),
+        };
+
+        req::update_pull(client, &self.base, owner, owner_repo, token, number, update).await?;
+
+        Ok(())
+    }
+
+    /// Replace the stack navigation in the bodies of the pull requests `numbers`
+    async fn navigate(
+        &self,
+        client: &Client,
+        token: &str,
+        owner: &str,
+        owner_repo: &str,
+        base: &str,
+        numbers: &[u64],
+    ) -> Result<(), GithubError> {
+        for number in numbers {
+            let pr = req::get_pull(client, &self.base, owner, owner_repo, token, *number).await?;
+            let body = pr.body.unwrap_or_default();
+            let navigated = with_navigation(&body, base, numbers, *number);
+
+            if navigated == body {
+                continue;
+            }
+
+            let update = req::UpdatePullRequest {
+                title: None,
+                body: Some(navigated),
+                base: None,
+            };
+
+            req::update_pull(
+                client, &self.base, owner, owner_repo, token, *number, update,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
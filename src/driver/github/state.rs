@@ -0,0 +1,84 @@
+use gix::Repository;
+use reqwest::Client;
+
+use super::{graphql, req, Github, GithubError, Remotes, PAGE_SIZE};
+use crate::tidal::{PullNumber, Req, Transition};
+
+impl Github {
+    pub(super) async fn transition_pull_request(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        transition: Transition,
+    ) -> Result<u64, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        // only a closed pull request that wasn't merged can be reopened
+        let number = match (number, transition) {
+            (None, Transition::Reopen) => {
+                let query = req::ListPulls {
+                    state: "closed",
+                    head: Some(&head),
+                    base: None,
+                    per_page: PAGE_SIZE,
+                    page: 1,
+                };
+
+                req::list_pulls(&client, &self.base, &owner, &owner_repo, &token, &query)
+                    .await?
+                    .into_iter()
+                    .find(|pr| pr.merged_at.is_none())
+                    .map(|pr| pr.number)
+                    .ok_or_else(|| GithubError::NoPullRequest(head.clone()))?
+            }
+
+            (number, _) => {
+                self.pull_number(&client, &token, &owner, &owner_repo, &head, number)
+                    .await?
+            }
+        };
+
+        match transition {
+            Transition::Close | Transition::Reopen => {
+                let state = req::SetState {
+                    state: if transition == Transition::Close {
+                        "closed"
+                    } else {
+                        "open"
+                    },
+                };
+
+                req::set_state(
+                    &client,
+                    &self.base,
+                    &owner,
+                    &owner_repo,
+                    &token,
+                    number,
+                    state,
+                )
+                .await?;
+            }
+
+            Transition::Ready | Transition::Draft => {
+                let pr =
+                    req::get_pull(&client, &self.base, &owner, &owner_repo, &token, number).await?;
+
+                if transition == Transition::Ready {
+                    graphql::mark_ready(&client, &self.base, &token, &pr.pull.node_id).await?;
+                } else {
+                    graphql::convert_to_draft(&client, &self.base, &token, &pr.pull.node_id).await?;
+                }
+            }
+        }
+
+        Ok(number)
+    }
+}
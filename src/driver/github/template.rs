@@ -0,0 +1,89 @@
+use std::{fs, path::Path};
+
+use gix::Repository;
+
+use super::GithubError;
+
+/// Single template locations, in the order github prefers them
+const FILES: &[&str] = &[
+    ".github/pull_request_template.md",
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    "pull_request_template.md",
+    "PULL_REQUEST_TEMPLATE.md",
+    "docs/pull_request_template.md",
+    "docs/PULL_REQUEST_TEMPLATE.md",
+];
+
+/// Directories holding multiple named templates
+const DIRS: &[&str] = &[
+    ".github/PULL_REQUEST_TEMPLATE",
+    "PULL_REQUEST_TEMPLATE",
+    "docs/PULL_REQUEST_TEMPLATE",
+];
+
+/// Find the pull request template of the worktree,
+/// `name` selects a template from a `PULL_REQUEST_TEMPLATE/` directory.
+pub fn find(repo: &Repository, name: Option<&str>) -> Result<Option<String>, GithubError> {
+    let Some(root) = repo.work_dir() else {
+        return Ok(None);
+    };
+
+    if let Some(name) = name {
+        return named(root, name)
+            .map(Some)
+            .ok_or_else(|| GithubError::NoTemplate(name.to_string()));
+    }
+
+    for file in FILES {
+        if let Ok(template) = fs::read_to_string(root.join(file)) {
+            return Ok(Some(template));
+        }
+    }
+
+    // only pick from a directory when there is no choice to make
+    let templates: Vec<_> = DIRS
+        .iter()
+        .flat_map(|dir| listing(&root.join(dir)))
+        .collect();
+    if let [template] = templates.as_slice() {
+        return Ok(fs::read_to_string(template).ok());
+    }
+
+    Ok(None)
+}
+
+fn named(root: &Path, name: &str) -> Option<String> {
+    for dir in DIRS {
+        let dir = root.join(dir);
+
+        for file in listing(&dir) {
+            let matches = file
+                .file_name()
+                .is_some_and(|f| f.eq_ignore_ascii_case(name))
+                || file
+                    .file_stem()
+                    .is_some_and(|f| f.eq_ignore_ascii_case(name));
+
+            if matches {
+                return fs::read_to_string(file).ok();
+            }
+        }
+    }
+
+    None
+}
+
+fn listing(dir: &Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+
+    files.sort();
+    files
+}
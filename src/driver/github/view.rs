@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use gix::Repository;
+use reqwest::Client;
+
+use super::{req, Github, GithubError, Remotes};
+use crate::tidal::{Check, Details, PullNumber, PullRequest, Req, Reviewer};
+
+impl Github {
+    pub(super) async fn view_pull_request(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Details, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        let number = self
+            .pull_number(&client, &token, &owner, &owner_repo, &head, number)
+            .await?;
+
+        let pr = req::get_pull(&client, &self.base, &owner, &owner_repo, &token, number).await?;
+        let reviews =
+            req::list_reviews(&client, &self.base, &owner, &owner_repo, &token, number).await?;
+        let checks = self
+            .checks(&client, &token, &owner, &owner_repo, &pr.pull.head.sha)
+            .await?;
+
+        // the latest verdict of each reviewer, comments don't override a verdict
+        let mut reviewers = BTreeMap::new();
+        for review in reviews {
+            let Some(user) = review.user else {
+                continue;
+            };
+
+            let state = review.state.to_lowercase();
+            if state != "commented" || !reviewers.contains_key(&user.login) {
+                reviewers.insert(user.login, state);
+            }
+        }
+
+        for user in pr.requested_reviewers {
+            reviewers.insert(user.login, "requested".to_string());
+        }
+
+        for team in pr.requested_teams {
+            reviewers.insert(format!("@{owner}/{}", team.slug), "requested".to_string());
+        }
+
+        Ok(Details {
+            body: pr.body.unwrap_or_default(),
+            reviewers: reviewers
+                .into_iter()
+                .map(|(name, state)| Reviewer { name, state })
+                .collect(),
+            checks,
+            mergeable: pr.mergeable,
+            merge_state: pr.mergeable_state,
+            pull: PullRequest::from(pr.pull),
+        })
+    }
+
+    pub(super) async fn pull_request_checks(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Vec<Check>, GithubError> {
+        let client = Client::new();
+        let token = self.token(&repo)?;
+
+        let remotes = Remotes::of(&repo, &req)?;
+        let (owner, owner_repo) = self.upstream(&client, &token, &remotes, false).await?;
+        let (head_owner, _) = remotes.fork;
+        let head = format!("{head_owner}:{}", req.from.branch);
+
+        let number = self
+            .pull_number(&client, &token, &owner, &owner_repo, &head, number)
+            .await?;
+
+        let pr = req::get_pull(&client, &self.base, &owner, &owner_repo, &token, number).await?;
+
+        self.checks(&client, &token, &owner, &owner_repo, &pr.pull.head.sha)
+            .await
+    }
+
+    /// Check runs and commit statuses of a commit
+    pub(super) async fn checks(
+        &self,
+        client: &Client,
+        token: &str,
+        owner: &str,
+        owner_repo: &str,
+        sha: &str,
+    ) -> Result<Vec<Check>, GithubError> {
+        let runs = req::check_runs(client, &self.base, owner, owner_repo, token, sha).await?;
+        let status =
+            req::combined_status(client, &self.base, owner, owner_repo, token, sha).await?;
+
+        let checks = runs
+            .check_runs
+            .into_iter()
+            .map(Check::from)
+            .chain(status.statuses.into_iter().map(Check::from))
+            .collect();
+
+        Ok(checks)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::tidal::{Common, Desc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version)]
+pub struct GitlabCli {
+    /// Title of the merge request
+    #[clap(short, long)]
+    pub title: String,
+
+    /// Description of merge request
+    #[clap(short, long)]
+    pub description: Option<Desc>,
+
+    /// Open page for merge request
+    #[clap(short, long)]
+    pub open: bool,
+
+    /// Draft merge request
+    #[clap(short = 'D', long)]
+    pub draft: bool,
+
+    /// May maintainer ammend
+    #[clap(short, long)]
+    pub fixup: bool,
+
+    /// Squash commits when merged
+    #[clap(short, long)]
+    pub squash: bool,
+
+    /// Remove source branch when merged
+    #[clap(short = 'x', long)]
+    pub remove_source_branch: bool,
+
+    #[clap(flatten)]
+    pub common: Common,
+}
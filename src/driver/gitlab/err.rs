@@ -0,0 +1,55 @@
+use crate::{cred::CredError, errors::Error};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum GitlabError {
+    FailedDescription,
+    Http(reqwest::Error),
+    Forbidden,
+    NotFound,
+    Conflict,
+    Validation,
+    NoToken(CredError),
+    FailedToOpen,
+}
+
+impl From<reqwest::Error> for GitlabError {
+    fn from(value: reqwest::Error) -> Self {
+        if let Some(status) = value.status() {
+            match status.into() {
+                403 => return GitlabError::Forbidden,
+                404 => return GitlabError::NotFound,
+                409 => return GitlabError::Conflict,
+                422 => return GitlabError::Validation,
+                _ => (),
+            }
+        }
+
+        GitlabError::Http(value)
+    }
+}
+
+impl std::error::Error for GitlabError {}
+
+impl Display for GitlabError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitlabError::Forbidden => write!(f, "not permitted to create merge request")?,
+            GitlabError::NotFound => write!(f, "project not found")?,
+            GitlabError::Conflict => write!(f, "merge request already exists")?,
+            GitlabError::Validation => write!(f, "merge request failed validation")?,
+            GitlabError::FailedDescription => write!(f, "failed to read description")?,
+            GitlabError::NoToken(e) => write!(f, "{e}")?,
+            GitlabError::Http(e) => write!(f, "http error: {e}")?,
+            GitlabError::FailedToOpen => write!(f, "failed to open merge request in browser")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for GitlabError {
+    fn status(&self) -> i32 {
+        3
+    }
+}
@@ -0,0 +1,188 @@
+use std::ffi::OsString;
+
+use super::{DriverError, Runner};
+use crate::cred::find_token;
+use crate::driver::gitlab::cli::GitlabCli;
+use crate::driver::gitlab::req::{CreateMergeRequest, ListMergeRequests};
+use crate::errors::SysErrorHandler;
+use crate::tidal::{ListFilter, PullRequest, Req, StateFilter};
+use clap::Parser;
+
+pub use err::GitlabError;
+use gix::remote::Direction;
+use gix::Repository;
+
+mod cli;
+mod err;
+mod req;
+
+const PAGE_SIZE: usize = 100;
+
+/// Create a merge request with gitlabs [merge request api](https://docs.gitlab.com/ee/api/merge_requests.html#create-mr)
+pub struct Gitlab {
+    /// Base use
+    pub base: String,
+}
+
+impl Gitlab {
+    async fn create_merge_request(
+        &self,
+        repo: Repository,
+        mut req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), GitlabError> {
+        let cli = GitlabCli::parse_from(args);
+        req.overwrite(&cli.common.refs);
+        let req = req.validate(&repo).handle_system_error();
+        req.push(&repo, &cli.common).handle_system_error();
+
+        let title = get_title(&cli);
+        let description = get_description(&cli)?;
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(GitlabError::NoToken)?;
+
+        let source = project_path(&repo, &req.from.remote)?;
+        let target = project_path(&repo, &req.to.remote)?;
+
+        let target_project_id = if source != target {
+            Some(
+                req::find_project(&client, &self.base, &target, &token)
+                    .await?
+                    .id,
+            )
+        } else {
+            None
+        };
+
+        let payload = CreateMergeRequest {
+            title,
+            source_branch: req.from.branch,
+            target_branch: req.to.branch,
+            target_project_id,
+            description,
+            remove_source_branch: cli.remove_source_branch,
+            squash: cli.squash,
+            allow_collaboration: cli.fixup,
+        };
+
+        let res = req::send_request(&client, &self.base, &source, &token, payload).await?;
+
+        if cli.open {
+            open::that(res.web_url).map_err(|_| GitlabError::FailedToOpen)?;
+        }
+
+        Ok(())
+    }
+
+    async fn list_merge_requests(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, GitlabError> {
+        let target = project_path(&repo, &req.to.remote)?;
+
+        let state = match filter.state {
+            StateFilter::Open => "opened",
+            StateFilter::Closed => "closed",
+            StateFilter::Merged => "merged",
+            StateFilter::All => "all",
+        };
+
+        let labels = if filter.labels.is_empty() {
+            None
+        } else {
+            Some(filter.labels.join(","))
+        };
+
+        let client = reqwest::Client::new();
+        let token = find_token(&repo, &self.base).map_err(GitlabError::NoToken)?;
+
+        let mut prs = Vec::new();
+        for page in 1.. {
+            let query = ListMergeRequests {
+                state,
+                author_username: filter.author.as_deref(),
+                source_branch: filter.head.as_deref(),
+                target_branch: filter.base.as_deref(),
+                labels: labels.clone(),
+                per_page: PAGE_SIZE,
+                page,
+            };
+
+            let batch =
+                req::list_merge_requests(&client, &self.base, &target, &token, &query).await?;
+            let last = batch.len() < PAGE_SIZE;
+
+            prs.extend(
+                batch
+                    .into_iter()
+                    .map(PullRequest::from)
+                    .filter(|pr| filter.matches(pr)),
+            );
+
+            if last || prs.len() >= filter.limit {
+                break;
+            }
+        }
+
+        prs.truncate(filter.limit);
+        Ok(prs)
+    }
+}
+
+fn get_title(cli: &GitlabCli) -> String {
+    if cli.draft {
+        format!("Draft: {}", cli.title)
+    } else {
+        cli.title.clone()
+    }
+}
+
+fn get_description(cli: &GitlabCli) -> Result<String, GitlabError> {
+    let body = if let Some(desc) = &cli.description {
+        match desc.read() {
+            Ok(s) => s,
+            Err(_) => return Err(GitlabError::FailedDescription),
+        }
+    } else {
+        "".to_string()
+    };
+    Ok(body)
+}
+
+/// Full project path of a remote, gitlab allows nested groups so this is not just owner/repo
+fn project_path(repo: &Repository, remote: &str) -> Result<String, GitlabError> {
+    let remote = repo.find_remote(remote).unwrap();
+    let url = remote.url(Direction::Push).unwrap();
+
+    let path = url.path.to_string();
+    let path = path.trim_start_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+
+    Ok(path.to_string())
+}
+
+#[async_trait::async_trait]
+impl Runner for Gitlab {
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.create_merge_request(repo, req, args).await?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, DriverError> {
+        Ok(self.list_merge_requests(repo, req, filter).await?)
+    }
+}
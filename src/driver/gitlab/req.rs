@@ -0,0 +1,165 @@
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::GitlabError;
+use crate::tidal::State;
+
+#[derive(Serialize, Debug)]
+pub struct CreateMergeRequest {
+    pub title: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_project_id: Option<u64>,
+    pub description: String,
+    pub remove_source_branch: bool,
+    pub squash: bool,
+    pub allow_collaboration: bool,
+}
+
+#[derive(Deserialize)]
+pub struct CreateMergeRequestResponse {
+    pub web_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct Project {
+    pub id: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ListMergeRequests<'a> {
+    pub state: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_username: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_branch: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_branch: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<String>,
+    pub per_page: usize,
+    pub page: usize,
+}
+
+#[derive(Deserialize)]
+pub struct MergeRequest {
+    pub iid: u64,
+    pub title: String,
+    pub author: User,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub labels: Vec<String>,
+    pub web_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct User {
+    pub username: String,
+}
+
+impl From<MergeRequest> for crate::tidal::PullRequest {
+    fn from(mr: MergeRequest) -> Self {
+        let state = match mr.state.as_str() {
+            "opened" => State::Open,
+            "merged" => State::Merged,
+            _ => State::Closed,
+        };
+
+        crate::tidal::PullRequest {
+            number: mr.iid,
+            title: mr.title,
+            author: mr.author.username,
+            head: mr.source_branch,
+            base: mr.target_branch,
+            state,
+            draft: mr.draft,
+            labels: mr.labels,
+            url: mr.web_url,
+        }
+    }
+}
+
+/// Url encode a project path (`group/subgroup/repo`) so it can be used as a project id
+fn project_id(project: &str) -> String {
+    project.replace('/', "%2F")
+}
+
+fn authorize(req: RequestBuilder, token: &str) -> RequestBuilder {
+    req.header("User-Agent", "Tidal")
+        .header("Accept", "application/json")
+        .header("PRIVATE-TOKEN", token)
+}
+
+pub async fn find_project(
+    client: &Client,
+    base: &str,
+    project: &str,
+    token: &str,
+) -> Result<Project, GitlabError> {
+    let url = format!("https://{base}/api/v4/projects/{}", project_id(project));
+
+    let res = authorize(client.get(url), token).send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: Project = res.json().await?;
+
+    Ok(res)
+}
+
+pub async fn send_request(
+    client: &Client,
+    base: &str,
+    project: &str,
+    token: &str,
+    req: CreateMergeRequest,
+) -> Result<CreateMergeRequestResponse, GitlabError> {
+    let url = format!(
+        "https://{base}/api/v4/projects/{}/merge_requests",
+        project_id(project)
+    );
+
+    let req = authorize(client.post(url), token).json(&req);
+
+    let res = req.send().await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: CreateMergeRequestResponse = res.json().await?;
+
+    Ok(res)
+}
+
+pub async fn list_merge_requests(
+    client: &Client,
+    base: &str,
+    project: &str,
+    token: &str,
+    query: &ListMergeRequests<'_>,
+) -> Result<Vec<MergeRequest>, GitlabError> {
+    let url = format!(
+        "https://{base}/api/v4/projects/{}/merge_requests",
+        project_id(project)
+    );
+
+    let res = authorize(client.get(url), token)
+        .query(query)
+        .send()
+        .await?;
+    if let Err(e) = res.error_for_status_ref() {
+        log::error!("failed request: {}", res.text().await.unwrap());
+        return Err(e.into());
+    }
+
+    let res: Vec<MergeRequest> = res.json().await?;
+
+    Ok(res)
+}
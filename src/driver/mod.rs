@@ -1,39 +1,305 @@
+use std::ffi::OsString;
+
 use gix::Repository;
 
-use crate::{errors::Error, tidal::Req};
+use crate::{
+    errors::Error,
+    tidal::{
+        Check, Conversation, Details, Head, ListFilter, MergeOptions, Merged, PullNumber,
+        PullRequest, Req, Review, Transition,
+    },
+};
 
-use self::github::GithubError;
+use self::{
+    azure::AzureError,
+    bitbucket::{BitbucketCloudError, BitbucketServerError},
+    email::EmailError,
+    gerrit::GerritError,
+    gitea::GiteaError,
+    github::GithubError,
+    gitlab::GitlabError,
+};
 
+mod azure;
+mod bitbucket;
 pub mod detect;
+mod email;
+mod gerrit;
+mod gitea;
 mod github;
+mod gitlab;
 
 pub enum Driver {
     Github(github::Github),
+    Gitlab(gitlab::Gitlab),
+    Gitea(gitea::Gitea),
+    BitbucketCloud(bitbucket::BitbucketCloud),
+    BitbucketServer(bitbucket::BitbucketServer),
+    Azure(azure::Azure),
+    Email(email::Email),
+    Gerrit(gerrit::Gerrit),
 }
 
 #[derive(Debug)]
 pub enum DriverError {
     Github(github::GithubError),
+    Gitlab(gitlab::GitlabError),
+    Gitea(gitea::GiteaError),
+    BitbucketCloud(bitbucket::BitbucketCloudError),
+    BitbucketServer(bitbucket::BitbucketServerError),
+    Azure(azure::AzureError),
+    Email(email::EmailError),
+    Gerrit(gerrit::GerritError),
+    Unsupported(&'static str),
 }
 
 #[async_trait::async_trait]
-pub trait Runner {
-    async fn run(self, repo: Repository, req: Req) -> Result<(), DriverError>;
+pub trait Runner: Send + Sync {
+    /// Create a pull request, `args` are parsed by the drivers own cli
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError>;
+
+    /// Pull requests of the repository matching `filter`
+    async fn list(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, DriverError> {
+        Err(DriverError::Unsupported("list"))
+    }
+
+    /// Details of pull request `number`, or of the one from the from branch
+    async fn view(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+    ) -> Result<Details, DriverError> {
+        Err(DriverError::Unsupported("view"))
+    }
+
+    /// Checks of the head of pull request `number`, or of the one from the from branch
+    async fn checks(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+    ) -> Result<Vec<Check>, DriverError> {
+        Err(DriverError::Unsupported("checks"))
+    }
+
+    /// Where the head of pull request `number` can be fetched from
+    async fn head(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: PullNumber,
+    ) -> Result<Head, DriverError> {
+        Err(DriverError::Unsupported("checkout"))
+    }
+
+    /// Merge pull request `number`, or the one from the from branch
+    async fn merge(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+        _opts: MergeOptions,
+    ) -> Result<Merged, DriverError> {
+        Err(DriverError::Unsupported("merge"))
+    }
+
+    /// Retarget the pull requests of the stack of the from branch after one of them merged
+    async fn restack(&self, _repo: Repository, _req: Req) -> Result<(), DriverError> {
+        Err(DriverError::Unsupported("restack"))
+    }
+
+    /// Close, reopen or change the draft state of pull request `number`,
+    /// or of the one from the from branch, returning its number
+    async fn transition(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+        transition: Transition,
+    ) -> Result<u64, DriverError> {
+        match transition {
+            Transition::Close => Err(DriverError::Unsupported("close")),
+            Transition::Reopen => Err(DriverError::Unsupported("reopen")),
+            Transition::Ready | Transition::Draft => Err(DriverError::Unsupported("ready")),
+        }
+    }
+
+    /// Submit a review on pull request `number`, or on the one from the from branch,
+    /// returning its number
+    async fn review(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+        _review: Review,
+    ) -> Result<u64, DriverError> {
+        Err(DriverError::Unsupported("review"))
+    }
+
+    /// Comment on pull request `number`, or on the one from the from branch,
+    /// returning the url of the comment
+    async fn comment(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+        _body: String,
+    ) -> Result<String, DriverError> {
+        Err(DriverError::Unsupported("comment"))
+    }
+
+    /// Discussion of pull request `number`, or of the one from the from branch
+    async fn comments(
+        &self,
+        _repo: Repository,
+        _req: Req,
+        _number: Option<PullNumber>,
+    ) -> Result<Conversation, DriverError> {
+        Err(DriverError::Unsupported("comments"))
+    }
 }
 
-#[async_trait::async_trait]
-impl Runner for Driver {
-    async fn run(self, repo: Repository, req: Req) -> Result<(), DriverError> {
+impl Driver {
+    fn runner(&self) -> &dyn Runner {
         match self {
-            Driver::Github(inner) => inner.run(repo, req).await,
+            Driver::Github(inner) => inner,
+            Driver::Gitlab(inner) => inner,
+            Driver::Gitea(inner) => inner,
+            Driver::BitbucketCloud(inner) => inner,
+            Driver::BitbucketServer(inner) => inner,
+            Driver::Azure(inner) => inner,
+            Driver::Email(inner) => inner,
+            Driver::Gerrit(inner) => inner,
         }
     }
 }
 
+#[async_trait::async_trait]
+impl Runner for Driver {
+    async fn create(
+        &self,
+        repo: Repository,
+        req: Req,
+        args: Vec<OsString>,
+    ) -> Result<(), DriverError> {
+        self.runner().create(repo, req, args).await
+    }
+
+    async fn list(
+        &self,
+        repo: Repository,
+        req: Req,
+        filter: ListFilter,
+    ) -> Result<Vec<PullRequest>, DriverError> {
+        self.runner().list(repo, req, filter).await
+    }
+
+    async fn view(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Details, DriverError> {
+        self.runner().view(repo, req, number).await
+    }
+
+    async fn checks(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Vec<Check>, DriverError> {
+        self.runner().checks(repo, req, number).await
+    }
+
+    async fn head(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: PullNumber,
+    ) -> Result<Head, DriverError> {
+        self.runner().head(repo, req, number).await
+    }
+
+    async fn merge(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        opts: MergeOptions,
+    ) -> Result<Merged, DriverError> {
+        self.runner().merge(repo, req, number, opts).await
+    }
+
+    async fn restack(&self, repo: Repository, req: Req) -> Result<(), DriverError> {
+        self.runner().restack(repo, req).await
+    }
+
+    async fn transition(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        transition: Transition,
+    ) -> Result<u64, DriverError> {
+        self.runner()
+            .transition(repo, req, number, transition)
+            .await
+    }
+
+    async fn review(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        review: Review,
+    ) -> Result<u64, DriverError> {
+        self.runner().review(repo, req, number, review).await
+    }
+
+    async fn comment(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+        body: String,
+    ) -> Result<String, DriverError> {
+        self.runner().comment(repo, req, number, body).await
+    }
+
+    async fn comments(
+        &self,
+        repo: Repository,
+        req: Req,
+        number: Option<PullNumber>,
+    ) -> Result<Conversation, DriverError> {
+        self.runner().comments(repo, req, number).await
+    }
+}
+
 impl Error for DriverError {
     fn status(&self) -> i32 {
         match self {
             DriverError::Github(inner) => inner.status(),
+            DriverError::Gitlab(inner) => inner.status(),
+            DriverError::Gitea(inner) => inner.status(),
+            DriverError::BitbucketCloud(inner) => inner.status(),
+            DriverError::BitbucketServer(inner) => inner.status(),
+            DriverError::Azure(inner) => inner.status(),
+            DriverError::Email(inner) => inner.status(),
+            DriverError::Gerrit(inner) => inner.status(),
+            DriverError::Unsupported(_) => 14,
         }
     }
 }
@@ -42,6 +308,14 @@ impl std::fmt::Display for DriverError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DriverError::Github(inner) => inner.fmt(f),
+            DriverError::Gitlab(inner) => inner.fmt(f),
+            DriverError::Gitea(inner) => inner.fmt(f),
+            DriverError::BitbucketCloud(inner) => inner.fmt(f),
+            DriverError::BitbucketServer(inner) => inner.fmt(f),
+            DriverError::Azure(inner) => inner.fmt(f),
+            DriverError::Email(inner) => inner.fmt(f),
+            DriverError::Gerrit(inner) => inner.fmt(f),
+            DriverError::Unsupported(op) => write!(f, "driver does not support {op}"),
         }
     }
 }
@@ -53,3 +327,45 @@ impl From<GithubError> for DriverError {
         DriverError::Github(value)
     }
 }
+
+impl From<GitlabError> for DriverError {
+    fn from(value: GitlabError) -> Self {
+        DriverError::Gitlab(value)
+    }
+}
+
+impl From<GiteaError> for DriverError {
+    fn from(value: GiteaError) -> Self {
+        DriverError::Gitea(value)
+    }
+}
+
+impl From<BitbucketCloudError> for DriverError {
+    fn from(value: BitbucketCloudError) -> Self {
+        DriverError::BitbucketCloud(value)
+    }
+}
+
+impl From<BitbucketServerError> for DriverError {
+    fn from(value: BitbucketServerError) -> Self {
+        DriverError::BitbucketServer(value)
+    }
+}
+
+impl From<AzureError> for DriverError {
+    fn from(value: AzureError) -> Self {
+        DriverError::Azure(value)
+    }
+}
+
+impl From<EmailError> for DriverError {
+    fn from(value: EmailError) -> Self {
+        DriverError::Email(value)
+    }
+}
+
+impl From<GerritError> for DriverError {
+    fn from(value: GerritError) -> Self {
+        DriverError::Gerrit(value)
+    }
+}
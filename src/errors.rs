@@ -37,3 +37,71 @@ impl Error for NotGitRepo {
         32
     }
 }
+
+#[derive(Debug, Copy, Clone)]
+pub struct FailedToOpen;
+
+impl std::fmt::Display for FailedToOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to open pull request in browser")?;
+        Ok(())
+    }
+}
+
+impl std::error::Error for FailedToOpen {}
+impl Error for FailedToOpen {
+    fn status(&self) -> i32 {
+        15
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FailedDescription;
+
+impl std::fmt::Display for FailedDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to read description")?;
+        Ok(())
+    }
+}
+
+impl std::error::Error for FailedDescription {}
+impl Error for FailedDescription {
+    fn status(&self) -> i32 {
+        19
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ChecksFailed;
+
+impl std::fmt::Display for ChecksFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "checks failed")?;
+        Ok(())
+    }
+}
+
+impl std::error::Error for ChecksFailed {}
+impl Error for ChecksFailed {
+    fn status(&self) -> i32 {
+        20
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ChecksTimeout;
+
+impl std::fmt::Display for ChecksTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out waiting for checks")?;
+        Ok(())
+    }
+}
+
+impl std::error::Error for ChecksTimeout {}
+impl Error for ChecksTimeout {
+    fn status(&self) -> i32 {
+        22
+    }
+}
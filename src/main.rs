@@ -1,10 +1,18 @@
-use driver::Runner;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use driver::{Driver, Runner};
 
 use crate::{
+    cli::{Cli, Command},
     driver::detect::find_driver,
-    errors::{NotGitRepo, SysErrorHandler},
+    errors::{
+        ChecksFailed, ChecksTimeout, FailedDescription, FailedToOpen, NotGitRepo, SysErrorHandler,
+    },
+    tidal::{CheckState, PullNumber, Req, Review, Transition},
 };
 
+mod cli;
 pub mod cred;
 mod driver;
 mod errors;
@@ -14,6 +22,8 @@ mod tidal;
 async fn main() {
     pretty_env_logger::init();
 
+    let cli = Cli::parse();
+
     let repo = gix::discover(".")
         .map_err(|_| NotGitRepo)
         .handle_system_error();
@@ -21,5 +31,211 @@ async fn main() {
     let req = crate::tidal::Req::build(&repo).handle_system_error();
     let driver = find_driver(&repo).handle_system_error();
 
-    driver.run(repo, req).await.handle_system_error();
+    match cli.command() {
+        Command::Create(args) => {
+            driver
+                .create(repo, req, args.argv())
+                .await
+                .handle_system_error();
+        }
+
+        Command::List(args) => {
+            let prs = driver
+                .list(repo, req, args.filter)
+                .await
+                .handle_system_error();
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&prs).unwrap());
+            } else {
+                print!("{}", crate::tidal::table(&prs));
+            }
+        }
+
+        Command::View(args) => {
+            let number = args.number;
+            let details = driver.view(repo, req, number).await.handle_system_error();
+
+            if args.web {
+                open::that(&details.pull.url)
+                    .map_err(|_| FailedToOpen)
+                    .handle_system_error();
+            } else if args.json {
+                println!("{}", serde_json::to_string_pretty(&details).unwrap());
+            } else {
+                print!("{}", crate::tidal::details(&details));
+            }
+        }
+
+        Command::Checks(args) => {
+            let number = args.number;
+            let interval = Duration::from_secs(args.interval);
+            let deadline = Instant::now() + Duration::from_secs(args.timeout);
+
+            let (checks, timed_out) = loop {
+                let checks = driver
+                    .checks(repo.clone(), req.clone(), number.clone())
+                    .await
+                    .handle_system_error();
+
+                let pending = checks
+                    .iter()
+                    .filter(|check| check.state == CheckState::Pending)
+                    .count();
+
+                // checks may only be reported a while after a push
+                if !args.watch || (pending == 0 && !checks.is_empty()) {
+                    break (checks, false);
+                }
+
+                if Instant::now() + interval > deadline {
+                    break (checks, true);
+                }
+
+                if checks.is_empty() {
+                    eprintln!("no checks reported yet");
+                } else {
+                    eprintln!("{pending}/{} checks pending", checks.len());
+                }
+                tokio::time::sleep(interval).await;
+            };
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&checks).unwrap());
+            } else {
+                print!("{}", crate::tidal::checks(&checks));
+            }
+
+            if checks.iter().any(|check| check.state.failed()) {
+                Err(ChecksFailed).handle_system_error()
+            }
+
+            if timed_out {
+                Err(ChecksTimeout).handle_system_error()
+            }
+        }
+
+        Command::Checkout(args) => {
+            let remote = req.to.remote.clone();
+            let head = driver
+                .head(repo.clone(), req, args.number)
+                .await
+                .handle_system_error();
+
+            let branch = crate::tidal::checkout(&repo, &remote, &head, args.branch.as_deref())
+                .handle_system_error();
+
+            eprintln!("switched to #{} on {branch}", head.number);
+        }
+
+        Command::Merge(args) => {
+            let mut req = req;
+            req.overwrite(&args.refs);
+
+            // the pull request of the from branch, resolved like create does
+            if args.number.is_none() {
+                req = req.validate(&repo).handle_system_error();
+            }
+
+            let delete = args.options.delete_branch;
+            let number = args.number;
+            let merged = driver
+                .merge(repo.clone(), req, number, args.options)
+                .await
+                .handle_system_error();
+
+            match &merged.sha {
+                Some(sha) => eprintln!("merged #{} as {sha}", merged.number),
+                None => eprintln!("auto-merge enabled for #{}", merged.number),
+            }
+
+            if delete && merged.sha.is_some() {
+                crate::tidal::delete_branch(&repo, &merged).handle_system_error();
+            }
+        }
+
+        Command::Restack => {
+            driver.restack(repo, req).await.handle_system_error();
+        }
+
+        Command::Close(args) => {
+            let number = args.number;
+            transition(driver, repo, req, number, Transition::Close).await;
+        }
+
+        Command::Reopen(args) => {
+            let number = args.number;
+            transition(driver, repo, req, number, Transition::Reopen).await;
+        }
+
+        Command::Ready(args) => {
+            let number = args.number;
+            let to = if args.undo {
+                Transition::Draft
+            } else {
+                Transition::Ready
+            };
+
+            transition(driver, repo, req, number, to).await;
+        }
+
+        Command::Review(args) => {
+            let number = args.number;
+            let verdict = args.verdict.verdict();
+            let review = Review::build(verdict, args.body.as_ref(), args.comments.as_deref())
+                .handle_system_error();
+
+            let number = driver
+                .review(repo, req, number, review)
+                .await
+                .handle_system_error();
+
+            eprintln!("reviewed #{number}");
+        }
+
+        Command::Comment(args) => {
+            let number = args.number;
+            let body = args
+                .body
+                .read()
+                .map_err(|_| FailedDescription)
+                .handle_system_error();
+
+            let url = driver
+                .comment(repo, req, number, body)
+                .await
+                .handle_system_error();
+
+            println!("{url}");
+        }
+
+        Command::Comments(args) => {
+            let number = args.number;
+            let conversation = driver
+                .comments(repo, req, number)
+                .await
+                .handle_system_error();
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&conversation).unwrap());
+            } else {
+                print!("{}", crate::tidal::conversation(&conversation));
+            }
+        }
+    }
+}
+
+async fn transition(
+    driver: Driver,
+    repo: gix::Repository,
+    req: Req,
+    number: Option<PullNumber>,
+    transition: Transition,
+) {
+    let number = driver
+        .transition(repo, req, number, transition)
+        .await
+        .handle_system_error();
+
+    eprintln!("{transition} #{number}");
 }
@@ -0,0 +1,181 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::{remote::Direction, ObjectId, Repository};
+
+use crate::errors::Error;
+
+use super::git::{branch_exists, git, GitError};
+
+/// Where the head of a pull request can be fetched from
+pub struct Head {
+    pub number: u64,
+    pub branch: String,
+    pub source: Source,
+    /// Ref the target repository keeps the head under, e.g. `refs/pull/12/head`
+    pub pull_ref: Option<String>,
+}
+
+/// Repository the pull request comes from
+pub enum Source {
+    /// The target repository
+    Target,
+    /// A fork of `owner` at `url`
+    Fork { owner: String, url: String },
+    /// A fork that has since been deleted
+    Deleted,
+}
+
+#[derive(Debug)]
+pub enum CheckoutError {
+    Git(GitError),
+    Fetch(Box<dyn std::error::Error + Send + Sync>),
+    NotFetched(String),
+    NoRemoteUrl(String),
+    Untracked(String),
+    Deleted,
+}
+
+impl std::fmt::Display for CheckoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckoutError::Git(err) => err.fmt(f)?,
+            CheckoutError::Fetch(err) => write!(f, "failed to fetch pull request: {err}")?,
+            CheckoutError::NotFetched(spec) => write!(f, "remote has no {spec}")?,
+            CheckoutError::NoRemoteUrl(remote) => write!(f, "remote {remote} has no url")?,
+            CheckoutError::Untracked(branch) => write!(
+                f,
+                "branch {branch} exists and does not track the pull request, choose another with --branch"
+            )?,
+            CheckoutError::Deleted => write!(f, "the repository of the pull request was deleted")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl From<GitError> for CheckoutError {
+    fn from(value: GitError) -> Self {
+        CheckoutError::Git(value)
+    }
+}
+
+impl std::error::Error for CheckoutError {}
+
+impl Error for CheckoutError {
+    fn status(&self) -> i32 {
+        16
+    }
+}
+
+fn fetch_error(e: impl std::error::Error + Send + Sync + 'static) -> CheckoutError {
+    CheckoutError::Fetch(Box::new(e))
+}
+
+/// Fetch the head of a pull request from `remote` (or its fork) and switch to it
+/// as `branch`, fast forwarding the branch if it already tracks the pull request.
+///
+/// The branch defaults to the pull request's branch, prefixed with the owner for forks
+/// so it can't collide with a branch of the target repository, and `pr-<number>` when
+/// the fork was deleted. It tracks the pull request's branch, or its pull ref when the
+/// fork was deleted, so `git pull` keeps working.
+pub fn checkout(
+    repo: &Repository,
+    remote: &str,
+    head: &Head,
+    branch: Option<&str>,
+) -> Result<String, CheckoutError> {
+    let local = match (branch, &head.source) {
+        (Some(branch), _) => branch.to_string(),
+        (None, Source::Target) => head.branch.clone(),
+        (None, Source::Fork { owner, .. }) => format!("{owner}-{}", head.branch),
+        (None, Source::Deleted) => format!("pr-{}", head.number),
+    };
+
+    let remote_url = repo
+        .find_remote(remote)
+        .ok()
+        .and_then(|found| found.url(Direction::Fetch).cloned())
+        .ok_or_else(|| CheckoutError::NoRemoteUrl(remote.to_string()))?;
+
+    let branch_ref = format!("refs/heads/{}", head.branch);
+    let (upstream_remote, upstream_merge) = match (&head.source, &head.pull_ref) {
+        (Source::Target, _) => (remote.to_string(), branch_ref.clone()),
+        (Source::Fork { url, .. }, _) => (url.clone(), branch_ref.clone()),
+        (Source::Deleted, Some(pull_ref)) => (remote.to_string(), pull_ref.clone()),
+        (Source::Deleted, None) => return Err(CheckoutError::Deleted),
+    };
+
+    let exists = branch_exists(repo, &local);
+    if exists && !tracks(repo, &local, &upstream_remote, &upstream_merge) {
+        return Err(CheckoutError::Untracked(local));
+    }
+
+    let id = match (&head.source, &head.pull_ref) {
+        (Source::Target, _) => {
+            let spec = format!("+{branch_ref}:refs/remotes/{remote}/{}", head.branch);
+            fetch(repo, remote_url, &spec)?
+        }
+        (_, Some(pull_ref)) => fetch(repo, remote_url, pull_ref)?,
+        (Source::Fork { url, .. }, None) => {
+            let url = gix::Url::try_from(url.as_str()).map_err(fetch_error)?;
+            fetch(repo, url, &branch_ref)?
+        }
+        (Source::Deleted, None) => return Err(CheckoutError::Deleted),
+    };
+    let id = id.to_string();
+
+    if exists {
+        git(repo, "switch", &[&local])?;
+        git(repo, "merge", &["--ff-only", &id])?;
+    } else {
+        git(repo, "switch", &["--create", &local, &id])?;
+    }
+
+    git(
+        repo,
+        "config",
+        &[&format!("branch.{local}.remote"), &upstream_remote],
+    )?;
+    git(
+        repo,
+        "config",
+        &[&format!("branch.{local}.merge"), &upstream_merge],
+    )?;
+
+    Ok(local)
+}
+
+/// Whether the local branch `branch` tracks `merge` of `remote`
+fn tracks(repo: &Repository, branch: &str, remote: &str, merge: &str) -> bool {
+    let snapshot = repo.config_snapshot();
+    let value = |key: &str| {
+        snapshot
+            .string(format!("branch.{branch}.{key}").as_str())
+            .map(|value| value.to_string())
+    };
+
+    value("remote").as_deref() == Some(remote) && value("merge").as_deref() == Some(merge)
+}
+
+/// Fetch `spec` from `url`, returning the commit it fetched
+fn fetch(repo: &Repository, url: gix::Url, spec: &str) -> Result<ObjectId, CheckoutError> {
+    let outcome = repo
+        .remote_at(url)
+        .map_err(fetch_error)?
+        .with_refspecs([spec], Direction::Fetch)
+        .map_err(fetch_error)?
+        .connect(Direction::Fetch)
+        .map_err(fetch_error)?
+        .prepare_fetch(gix::progress::Discard, Default::default())
+        .map_err(fetch_error)?
+        .receive(gix::progress::Discard, &AtomicBool::new(false))
+        .map_err(fetch_error)?;
+
+    outcome
+        .ref_map
+        .mappings
+        .iter()
+        .find_map(|mapping| mapping.remote.as_id())
+        .map(|id| id.to_owned())
+        .ok_or_else(|| CheckoutError::NotFetched(spec.to_string()))
+}
@@ -0,0 +1,89 @@
+use std::io::IsTerminal;
+
+use serde::Serialize;
+
+use super::pull::{bold, markdown};
+
+/// The discussion of a pull request
+#[derive(Serialize, Debug, Clone)]
+pub struct Conversation {
+    /// Comments and review summaries, oldest first
+    pub comments: Vec<Comment>,
+    /// Review threads on lines of the diff
+    pub threads: Vec<Thread>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Comment {
+    pub author: String,
+    pub body: String,
+    pub created_at: String,
+    /// State of the review the comment summarizes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Thread {
+    pub path: String,
+    pub line: Option<u64>,
+    pub resolved: bool,
+    pub outdated: bool,
+    pub comments: Vec<Comment>,
+}
+
+/// Render the discussion of a pull request for the terminal
+pub fn conversation(conversation: &Conversation) -> String {
+    let tty = std::io::stdout().is_terminal();
+    let mut out = String::new();
+
+    for comment in &conversation.comments {
+        render(&mut out, comment, "", tty);
+    }
+
+    for thread in &conversation.threads {
+        let location = match thread.line {
+            Some(line) => format!("{}:{line}", thread.path),
+            None => thread.path.clone(),
+        };
+
+        let mut state = vec![if thread.resolved {
+            "resolved"
+        } else {
+            "unresolved"
+        }];
+        if thread.outdated {
+            state.push("outdated");
+        }
+
+        out.push_str(&format!(
+            "{} ({})\n",
+            bold(&location, tty),
+            state.join(", ")
+        ));
+
+        for comment in &thread.comments {
+            render(&mut out, comment, "  ", tty);
+        }
+    }
+
+    out
+}
+
+fn render(out: &mut String, comment: &Comment, indent: &str, tty: bool) {
+    let author = bold(&comment.author, tty);
+    match &comment.review {
+        Some(state) => out.push_str(&format!(
+            "{indent}{author} {state} - {}\n",
+            comment.created_at
+        )),
+        None => out.push_str(&format!("{indent}{author} - {}\n", comment.created_at)),
+    }
+
+    for line in markdown(&comment.body, tty).lines() {
+        out.push_str(format!("{indent}  {line}").trim_end());
+        out.push('\n');
+    }
+
+    out.push('\n');
+}
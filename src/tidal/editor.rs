@@ -0,0 +1,188 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use gix::Repository;
+
+use crate::errors::Error;
+
+use super::Req;
+
+/// Everything below this line is dropped, so markdown headings survive unlike `#` comments
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+const HELP: &str = "\
+# Do not modify or remove the line above.
+# Everything below it will be ignored.
+# The first line is the title and the rest is the description,
+# an empty message aborts the pull request.";
+
+const BODY_HELP: &str = "\
+# Do not modify or remove the line above.
+# Everything below it will be ignored.
+# An empty message aborts.";
+
+#[derive(Debug)]
+pub enum EditorError {
+    Io(std::io::Error),
+    Spawn(std::io::Error),
+    Failed(std::process::ExitStatus),
+    Empty,
+}
+
+impl std::fmt::Display for EditorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditorError::Io(err) => write!(f, "failed to prepare message: {err}")?,
+            EditorError::Spawn(err) => write!(f, "failed to run editor: {err}")?,
+            EditorError::Failed(status) => write!(f, "editor failed: {status}")?,
+            EditorError::Empty => write!(f, "aborting due to empty message")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for EditorError {}
+
+impl Error for EditorError {
+    fn status(&self) -> i32 {
+        13
+    }
+}
+
+/// Title and body written in the editor
+pub struct Composed {
+    pub title: String,
+    pub body: String,
+}
+
+impl Req {
+    /// Compose a title and body in the editor,
+    /// prefilled with `title`, `body` and the commits and diffstat of the request
+    pub fn compose(
+        &self,
+        repo: &Repository,
+        title: &str,
+        body: &str,
+    ) -> Result<Composed, EditorError> {
+        let mut content = format!("{title}\n\n{body}\n\n{SCISSORS}\n{HELP}\n#\n");
+        content.push_str(&format!(
+            "# Merging {} into {}\n",
+            self.from.branch, self.to.branch
+        ));
+
+        if let Ok(commits) = self.commits(repo) {
+            content.push_str("#\n# Commits:\n");
+            for commit in commits {
+                content.push_str(&format!(
+                    "#   {} {}\n",
+                    commit.id.to_hex_with_len(7),
+                    commit.subject
+                ));
+            }
+        }
+
+        if let Some(diffstat) = diffstat(repo, self) {
+            content.push_str("#\n# Changes:\n");
+            for line in diffstat.lines() {
+                content.push_str(&format!("# {line}\n"));
+            }
+        }
+
+        let text = edit(Some(repo), &message_path(Some(repo)), &content)?;
+        let (title, body) = text.split_once('\n').unwrap_or((&text, ""));
+
+        Ok(Composed {
+            title: title.trim().to_string(),
+            body: body.trim().to_string(),
+        })
+    }
+}
+
+/// Write a description in the editor without any request context
+pub fn edit_body() -> Result<String, EditorError> {
+    let repo = gix::discover(".").ok();
+    let content = format!("\n\n{SCISSORS}\n{BODY_HELP}\n");
+
+    edit(repo.as_ref(), &message_path(repo.as_ref()), &content)
+}
+
+/// The editor git would use: `$GIT_EDITOR`, `core.editor`, `$VISUAL` then `$EDITOR`
+fn editor(repo: Option<&Repository>) -> String {
+    if let Ok(editor) = std::env::var("GIT_EDITOR") {
+        return editor;
+    }
+
+    if let Some(repo) = repo {
+        if let Some(editor) = repo.config_snapshot().string("core.editor") {
+            return editor.to_string();
+        }
+    }
+
+    for var in ["VISUAL", "EDITOR"] {
+        if let Ok(editor) = std::env::var(var) {
+            return editor;
+        }
+    }
+
+    "vi".to_string()
+}
+
+fn message_path(repo: Option<&Repository>) -> PathBuf {
+    match repo {
+        Some(repo) => repo.git_dir().join("TIDAL_EDITMSG"),
+        None => std::env::temp_dir().join("TIDAL_EDITMSG"),
+    }
+}
+
+fn edit(repo: Option<&Repository>, path: &Path, content: &str) -> Result<String, EditorError> {
+    fs::write(path, content).map_err(EditorError::Io)?;
+
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor(repo)))
+        .arg("editor")
+        .arg(path)
+        .status()
+        .map_err(EditorError::Spawn)?;
+
+    if !status.success() {
+        return Err(EditorError::Failed(status));
+    }
+
+    let text = fs::read_to_string(path).map_err(EditorError::Io)?;
+    let text = match text.find(SCISSORS) {
+        Some(i) => &text[..i],
+        None => &text,
+    };
+
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(EditorError::Empty);
+    }
+
+    Ok(text.to_string())
+}
+
+fn diffstat(repo: &Repository, req: &Req) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(repo.work_dir().unwrap_or(repo.git_dir()))
+        .arg("diff")
+        .arg("--stat")
+        .arg(format!("{}...{}", req.to.branch, req.from.branch))
+        .output()
+        .ok()?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return None;
+    }
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string(),
+    )
+}
@@ -0,0 +1,113 @@
+//! Throwaway repositories for tests
+
+use std::{
+    cell::Cell,
+    fs,
+    path::PathBuf,
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use gix::{ObjectId, Repository};
+
+use super::{Ref, Req};
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// A repository in a temporary directory, removed on drop
+pub struct Fixture {
+    pub dir: PathBuf,
+    time: Cell<u64>,
+}
+
+impl Fixture {
+    /// An empty repository on `main`
+    pub fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "tidal-fixture-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        let fixture = Fixture {
+            dir,
+            time: Cell::new(1_700_000_000),
+        };
+        fixture.git(&["init", "-q", "-b", "main"]);
+        fixture
+    }
+
+    /// Run git with a fixed identity and a clock that advances a minute per call
+    pub fn git(&self, args: &[&str]) -> String {
+        let time = self.time.get() + 60;
+        self.time.set(time);
+        let date = format!("{time} +0000");
+
+        let output = Command::new("git")
+            .current_dir(&self.dir)
+            .args(args)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_AUTHOR_NAME", "A U Thor")
+            .env("GIT_AUTHOR_EMAIL", "author@example.com")
+            .env("GIT_COMMITTER_NAME", "A U Thor")
+            .env("GIT_COMMITTER_EMAIL", "author@example.com")
+            .env("GIT_AUTHOR_DATE", &date)
+            .env("GIT_COMMITTER_DATE", &date)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    /// An empty commit with `message` on the current branch
+    pub fn commit(&self, message: &str) -> ObjectId {
+        self.git(&["commit", "-q", "--allow-empty", "-m", message]);
+        self.id("HEAD")
+    }
+
+    pub fn id(&self, rev: &str) -> ObjectId {
+        ObjectId::from_hex(self.git(&["rev-parse", rev]).trim().as_bytes()).unwrap()
+    }
+
+    /// Commits of `git rev-list <args>`, newest first
+    pub fn rev_list(&self, args: &[&str]) -> Vec<ObjectId> {
+        let mut cmd = vec!["rev-list"];
+        cmd.extend(args);
+
+        self.git(&cmd)
+            .lines()
+            .map(|line| ObjectId::from_hex(line.as_bytes()).unwrap())
+            .collect()
+    }
+
+    pub fn repo(&self) -> Repository {
+        gix::open(&self.dir).unwrap()
+    }
+
+    /// A request from `from` to `main` on `origin`
+    pub fn req(&self, from: &str) -> Req {
+        Req {
+            from: Ref {
+                branch: from.to_string(),
+                remote: "origin".to_string(),
+            },
+            to: Ref {
+                branch: "main".to_string(),
+                remote: "origin".to_string(),
+            },
+        }
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
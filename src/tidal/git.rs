@@ -0,0 +1,86 @@
+use std::process::Command;
+
+use gix::{remote::Direction, Repository};
+
+use crate::errors::Error;
+
+#[derive(Debug)]
+pub enum GitError {
+    Spawn(std::io::Error),
+    Failed(&'static str, std::process::ExitStatus),
+    RemoteMismatch(String, String),
+}
+
+impl std::fmt::Display for GitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitError::Spawn(err) => write!(f, "failed to run git: {err}")?,
+            GitError::Failed(cmd, status) => write!(f, "git {cmd} failed: {status}")?,
+            GitError::RemoteMismatch(name, url) => write!(
+                f,
+                "remote {name} already exists, but doesn't point to {url}"
+            )?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for GitError {}
+
+impl Error for GitError {
+    fn status(&self) -> i32 {
+        21
+    }
+}
+
+/// Run `git <cmd> <args>` in the work tree of `repo`
+pub(super) fn git(repo: &Repository, cmd: &'static str, args: &[&str]) -> Result<(), GitError> {
+    let status = Command::new("git")
+        .current_dir(repo.work_dir().unwrap_or(repo.git_dir()))
+        .arg(cmd)
+        .args(args)
+        .status()
+        .map_err(GitError::Spawn)?;
+
+    if !status.success() {
+        return Err(GitError::Failed(cmd, status));
+    }
+
+    Ok(())
+}
+
+/// Whether the local branch `name` exists
+pub(super) fn branch_exists(repo: &Repository, name: &str) -> bool {
+    repo.try_find_reference(format!("refs/heads/{name}").as_str())
+        .ok()
+        .flatten()
+        .is_some()
+}
+
+/// Add the remote `name` at `url`, unless there already is one by that name
+/// for the same repository
+pub fn add_remote(repo: &Repository, name: &str, url: &str) -> Result<(), GitError> {
+    let mismatch = || GitError::RemoteMismatch(name.to_string(), url.to_string());
+
+    let Ok(remote) = repo.find_remote(name) else {
+        return git(repo, "remote", &["add", name, url]);
+    };
+
+    let existing = remote.url(Direction::Fetch).ok_or_else(mismatch)?;
+    let wanted = gix::url::parse(url.into()).map_err(|_| mismatch())?;
+
+    // the same repository over ssh or https
+    if existing.host() != wanted.host() || repo_path(existing) != repo_path(&wanted) {
+        return Err(mismatch());
+    }
+
+    Ok(())
+}
+
+fn repo_path(url: &gix::Url) -> String {
+    let path = url.path.to_string();
+    let path = path.trim_start_matches('/');
+
+    path.strip_suffix(".git").unwrap_or(path).to_string()
+}
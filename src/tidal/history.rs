@@ -0,0 +1,267 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use gix::{ObjectId, Repository};
+
+use crate::errors::Error;
+
+use super::Req;
+
+#[derive(Debug)]
+pub enum LogError {
+    Git(Box<dyn std::error::Error + Send + Sync>),
+    NoCommits,
+}
+
+impl std::fmt::Display for LogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogError::Git(err) => write!(f, "failed to read history: {err}")?,
+            LogError::NoCommits => write!(f, "no commits between branches")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for LogError {}
+
+impl Error for LogError {
+    fn status(&self) -> i32 {
+        12
+    }
+}
+
+pub(super) fn git_error(e: impl std::error::Error + Send + Sync + 'static) -> LogError {
+    LogError::Git(Box::new(e))
+}
+
+/// Commits reachable from `tip` but not from `hidden`, newest first.
+///
+/// Both sides are walked by commit date and the walk stops shortly after only commits
+/// reachable from `hidden` are queued, so it costs the commits since the merge-base
+/// rather than the whole history.
+pub(super) fn range(
+    repo: &Repository,
+    tip: ObjectId,
+    hidden: ObjectId,
+) -> Result<Vec<ObjectId>, LogError> {
+    const TIP: u8 = 1;
+    const HIDDEN: u8 = 2;
+    const SLOP: usize = 5;
+
+    let mut flags: HashMap<ObjectId, u8> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for (id, flag) in [(tip, TIP), (hidden, HIDDEN)] {
+        *flags.entry(id).or_default() |= flag;
+        queue.push((commit_time(repo, id)?, id));
+    }
+
+    let mut walked = HashSet::new();
+    let mut order = Vec::new();
+    let mut slop = SLOP;
+    while let Some((_, id)) = queue.pop() {
+        if walked.insert(id) {
+            order.push(id);
+        }
+
+        let flag = flags[&id];
+        let object = repo.find_object(id).map_err(git_error)?;
+        let commit = object.try_into_commit().map_err(git_error)?;
+
+        for parent in commit.parent_ids() {
+            let parent = parent.detach();
+            let entry = flags.entry(parent).or_default();
+
+            // revisit commits that became hidden so their ancestors follow
+            if *entry | flag != *entry {
+                *entry |= flag;
+                queue.push((commit_time(repo, parent)?, parent));
+            }
+        }
+
+        // keep going for a few commits in case of clock skew, like git does
+        if queue.iter().all(|(_, id)| flags[id] & HIDDEN != 0) {
+            if slop == 0 {
+                break;
+            }
+            slop -= 1;
+        } else {
+            slop = SLOP;
+        }
+    }
+
+    Ok(order.into_iter().filter(|id| flags[id] == TIP).collect())
+}
+
+fn commit_time(repo: &Repository, id: ObjectId) -> Result<i64, LogError> {
+    let object = repo.find_object(id).map_err(git_error)?;
+    let commit = object.try_into_commit().map_err(git_error)?;
+    let time = commit.time().map_err(git_error)?;
+
+    Ok(time.seconds)
+}
+
+pub struct Commit {
+    pub id: ObjectId,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Title and body derived from the commits of a request
+pub struct Summary {
+    pub title: String,
+    pub body: String,
+}
+
+impl Req {
+    /// Commits reachable from the from branch but not the to branch, oldest first
+    pub fn commits(&self, repo: &Repository) -> Result<Vec<Commit>, LogError> {
+        let from = repo
+            .rev_parse_single(self.from.branch.as_str())
+            .map_err(git_error)?;
+        let to = repo
+            .rev_parse_single(self.to.branch.as_str())
+            .map_err(git_error)?;
+
+        let mut commits = Vec::new();
+        for id in range(repo, from.detach(), to.detach())? {
+            let object = repo.find_object(id).map_err(git_error)?;
+            let commit = object.try_into_commit().map_err(git_error)?;
+            let message = commit.message().map_err(git_error)?;
+
+            commits.push(Commit {
+                id,
+                subject: message.summary().to_string(),
+                body: message
+                    .body()
+                    .map(|body| body.to_string())
+                    .unwrap_or_default(),
+            });
+        }
+
+        commits.reverse();
+        Ok(commits)
+    }
+
+    /// The subject and body of a single commit,
+    /// or the from branch and all commit bodies for multiple commits
+    pub fn summary(&self, repo: &Repository) -> Result<Summary, LogError> {
+        let commits = self.commits(repo)?;
+
+        match commits.as_slice() {
+            [] => Err(LogError::NoCommits),
+            [commit] => Ok(Summary {
+                title: commit.subject.clone(),
+                body: commit.body.trim().to_string(),
+            }),
+            commits => Ok(Summary {
+                title: self.from.branch.clone(),
+                body: commits
+                    .iter()
+                    .map(|commit| commit.body.trim())
+                    .filter(|body| !body.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::range;
+    use crate::tidal::fixture::Fixture;
+
+    /// `range` agrees with `git rev-list main..feature`
+    fn assert_range(fixture: &Fixture) {
+        let repo = fixture.repo();
+        let walked = range(&repo, fixture.id("feature"), fixture.id("main")).unwrap();
+        let expected = fixture.rev_list(&["main..feature"]);
+
+        assert_eq!(
+            walked.iter().collect::<HashSet<_>>(),
+            expected.iter().collect::<HashSet<_>>()
+        );
+        assert_eq!(walked.len(), expected.len());
+    }
+
+    #[test]
+    fn diverged() {
+        let fixture = Fixture::new();
+        fixture.commit("base");
+        fixture.git(&["switch", "-q", "-c", "feature"]);
+        let first = fixture.commit("first");
+        let second = fixture.commit("second");
+        fixture.git(&["switch", "-q", "main"]);
+        fixture.commit("upstream");
+
+        let repo = fixture.repo();
+        let walked = range(&repo, fixture.id("feature"), fixture.id("main")).unwrap();
+
+        assert_eq!(walked, vec![second, first]);
+        assert_range(&fixture);
+    }
+
+    #[test]
+    fn base_merged_into_branch() {
+        let fixture = Fixture::new();
+        fixture.commit("base");
+        fixture.git(&["switch", "-q", "-c", "feature"]);
+        fixture.commit("first");
+        fixture.git(&["switch", "-q", "main"]);
+        fixture.commit("upstream one");
+        fixture.commit("upstream two");
+        fixture.git(&["switch", "-q", "feature"]);
+        fixture.git(&["merge", "-q", "--no-ff", "-m", "merge main", "main"]);
+        fixture.commit("second");
+
+        assert_range(&fixture);
+        assert_eq!(fixture.rev_list(&["main..feature"]).len(), 3);
+    }
+
+    #[test]
+    fn fast_forward_and_empty() {
+        let fixture = Fixture::new();
+        fixture.commit("base");
+        fixture.git(&["branch", "feature"]);
+
+        let repo = fixture.repo();
+        let same = range(&repo, fixture.id("feature"), fixture.id("main")).unwrap();
+        assert!(same.is_empty());
+
+        fixture.commit("upstream");
+        let behind = range(&repo, fixture.id("feature"), fixture.id("main")).unwrap();
+        assert!(behind.is_empty());
+        assert_range(&fixture);
+    }
+
+    #[test]
+    fn summary_of_one_commit() {
+        let fixture = Fixture::new();
+        fixture.commit("base");
+        fixture.git(&["switch", "-q", "-c", "feature"]);
+        fixture.commit("Add parser\n\nParses things.\n");
+
+        let summary = fixture.req("feature").summary(&fixture.repo()).unwrap();
+
+        assert_eq!(summary.title, "Add parser");
+        assert_eq!(summary.body, "Parses things.");
+    }
+
+    #[test]
+    fn summary_of_several_commits() {
+        let fixture = Fixture::new();
+        fixture.commit("base");
+        fixture.git(&["switch", "-q", "-c", "feature"]);
+        fixture.commit("Add parser\n\nParses things.");
+        fixture.commit("Fix typo");
+        fixture.commit("Handle empty input\n\nNo more panics.");
+
+        let summary = fixture.req("feature").summary(&fixture.repo()).unwrap();
+
+        assert_eq!(summary.title, "feature");
+        assert_eq!(summary.body, "Parses things.\n\nNo more panics.");
+    }
+}
@@ -0,0 +1,91 @@
+use clap::{Args, ValueEnum};
+use gix::Repository;
+
+use super::git::{branch_exists, git, GitError};
+
+/// How the commits of a pull request land on its base
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MergeOptions {
+    /// How to merge the pull request
+    #[clap(short, long, value_enum, default_value_t = Strategy::Merge)]
+    pub strategy: Strategy,
+
+    /// Title of the merge or squash commit
+    #[clap(short, long)]
+    pub title: Option<String>,
+
+    /// Message of the merge or squash commit
+    #[clap(short, long)]
+    pub message: Option<String>,
+
+    /// Only merge if the head of the pull request is still this commit
+    #[clap(long, value_name = "SHA")]
+    pub head: Option<String>,
+
+    /// Delete the branch of the pull request, remote and local, after merging
+    #[clap(short, long)]
+    pub delete_branch: bool,
+
+    /// Enable auto-merge instead when checks are still pending
+    #[clap(short = 'A', long)]
+    pub auto: bool,
+}
+
+/// Result of merging a pull request
+pub struct Merged {
+    pub number: u64,
+    /// Branch of the pull request
+    pub branch: String,
+    /// Branch the pull request was merged into
+    pub base: String,
+    /// Commit the head of the pull request was at
+    pub head: String,
+    /// Owner of the fork the pull request came from
+    pub fork: Option<String>,
+    /// The merge commit, `None` when auto-merge was enabled instead
+    pub sha: Option<String>,
+}
+
+/// Delete the local branch of a merged pull request, switching to its base if it's checked out.
+///
+/// The branch is the pull request's branch, or `<owner>-<branch>` for forks as named by checkout,
+/// and it's only deleted while it's still at the merged head so no commits are lost.
+pub fn delete_branch(repo: &Repository, merged: &Merged) -> Result<(), GitError> {
+    let mut names = vec![merged.branch.clone()];
+    if let Some(owner) = &merged.fork {
+        names.insert(0, format!("{owner}-{}", merged.branch));
+    }
+
+    for name in names {
+        if name == merged.base || !branch_exists(repo, &name) {
+            continue;
+        }
+
+        let tip = repo
+            .rev_parse_single(name.as_str())
+            .map(|id| id.to_string())
+            .ok();
+
+        if tip.as_deref() != Some(merged.head.as_str()) {
+            eprintln!("kept branch {name}, it is not at the merged head");
+            continue;
+        }
+
+        if let Ok(Some(head)) = repo.head_name() {
+            if head.shorten() == name {
+                git(repo, "switch", &[&merged.base])?;
+            }
+        }
+
+        return git(repo, "branch", &["-D", &name]);
+    }
+
+    Ok(())
+}
@@ -7,12 +7,51 @@ use std::{
 
 use clap::Parser;
 
+mod checkout;
+mod comment;
+mod editor;
+#[cfg(test)]
+mod fixture;
+mod git;
+mod history;
+mod merge;
+mod pull;
+mod push;
 mod req;
+mod review;
+mod stack;
 
+pub use checkout::{checkout, Head, Source};
+pub use comment::{conversation, Comment, Conversation, Thread};
+pub use git::add_remote;
+pub use history::Summary;
+pub use merge::{delete_branch, MergeOptions, Merged, Strategy};
+pub use pull::{
+    checks, details, table, Check, CheckState, Details, ListFilter, PullNumber, PullRequest,
+    Reviewer, State, StateFilter, Transition,
+};
 pub use req::{Ref, Req, ReqError};
+pub use review::{Review, Verdict};
+pub use stack::with_navigation;
 
 #[derive(Parser, Debug)]
 pub struct Common {
+    #[clap(flatten)]
+    pub refs: Refs,
+    /// Push the branch to merge from before creating the pull request
+    #[clap(long, overrides_with = "no_push")]
+    pub push: bool,
+    /// Do not push the branch to merge from (overrides tidal.push)
+    #[clap(long, overrides_with = "push")]
+    pub no_push: bool,
+    /// Push with --force-with-lease
+    #[clap(long)]
+    pub force_with_lease: bool,
+}
+
+/// The branches and remotes of a request
+#[derive(Parser, Debug)]
+pub struct Refs {
     /// The branch to merge from
     #[clap(short = 'b', long = "from-branch")]
     pub from_branch: Option<String>,
@@ -27,11 +66,15 @@ pub struct Common {
     pub to_remote: Option<String>,
 }
 
+/// Text given as itself, `-` for stdin, `@path` for a file or `@edit` for the editor.
+///
+/// A file named `edit` is read with `@./edit`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Desc {
     Inline(String),
     Stdin,
     File(PathBuf),
+    Editor,
 }
 
 impl FromStr for Desc {
@@ -42,6 +85,10 @@ impl FromStr for Desc {
             return Ok(Desc::Stdin);
         }
 
+        if s == "@edit" {
+            return Ok(Desc::Editor);
+        }
+
         if let Some(s) = s.strip_prefix('@') {
             return Ok(Desc::File(PathBuf::from(s)));
         }
@@ -60,6 +107,35 @@ impl Desc {
                 Ok(str)
             }
             Desc::File(path) => fs::read_to_string(&path),
+            Desc::Editor => editor::edit_body().map_err(io::Error::other),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Desc;
+
+    fn parse(s: &str) -> Desc {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn descriptions() {
+        assert_eq!(parse("-"), Desc::Stdin);
+        assert_eq!(
+            parse("Adds a parser"),
+            Desc::Inline("Adds a parser".to_string())
+        );
+        assert_eq!(parse("@notes.md"), Desc::File(PathBuf::from("notes.md")));
+    }
+
+    #[test]
+    fn editor_and_file_named_edit() {
+        assert_eq!(parse("@edit"), Desc::Editor);
+        assert_eq!(parse("@./edit"), Desc::File(PathBuf::from("./edit")));
+        assert_eq!(parse("@edit.md"), Desc::File(PathBuf::from("edit.md")));
+    }
+}
@@ -0,0 +1,534 @@
+use std::{io::IsTerminal, str::FromStr, time::Duration};
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+/// A pull request as reported by a driver
+#[derive(Serialize, Debug, Clone)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub head: String,
+    pub base: String,
+    pub state: State,
+    pub draft: bool,
+    pub labels: Vec<String>,
+    pub url: String,
+}
+
+/// A pull request with everything needed to review its state
+#[derive(Serialize, Debug, Clone)]
+pub struct Details {
+    #[serde(flatten)]
+    pub pull: PullRequest,
+    pub body: String,
+    pub reviewers: Vec<Reviewer>,
+    pub checks: Vec<Check>,
+    pub mergeable: Option<bool>,
+    pub merge_state: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Reviewer {
+    pub name: String,
+    pub state: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub state: CheckState,
+    pub url: Option<String>,
+    /// Seconds the check ran (so far)
+    pub duration: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckState {
+    Pending,
+    Success,
+    Failure,
+    Cancelled,
+    Skipped,
+    Neutral,
+}
+
+/// A pull request given by number (`12`, `#12`) or by url
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullNumber {
+    pub number: u64,
+    /// Path of the repository a url points into (`owner/repo`, `group/project`)
+    pub repo: Option<String>,
+}
+
+/// Path segments pull request urls put between the repository and the number
+const URL_MARKERS: [&str; 5] = [
+    "pull",
+    "pulls",
+    "pull-requests",
+    "pullrequest",
+    "merge_requests",
+];
+
+impl PullNumber {
+    /// Whether the pull request can belong to the repository at `path`,
+    /// a bare number belongs to any repository
+    pub fn belongs_to(&self, path: &str) -> bool {
+        match &self.repo {
+            Some(repo) => repo.eq_ignore_ascii_case(path),
+            None => true,
+        }
+    }
+}
+
+impl FromStr for PullNumber {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s.strip_prefix('#').unwrap_or(s);
+        if let Ok(number) = number.parse() {
+            return Ok(PullNumber { number, repo: None });
+        }
+
+        let invalid = || format!("not a pull request number or url: {s}");
+
+        // urls name the repository before the number:
+        // owner/repo/pull/12, group/project/-/merge_requests/12, project/_git/repo/pullrequest/12
+        let (_, rest) = s.split_once("://").ok_or_else(invalid)?;
+        let path = rest.split(['#', '?']).next().unwrap_or_default();
+        let segments: Vec<&str> = path
+            .split('/')
+            .skip(1)
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        let marker = segments
+            .iter()
+            .position(|segment| URL_MARKERS.contains(segment))
+            .ok_or_else(invalid)?;
+
+        let number = segments
+            .get(marker + 1)
+            .and_then(|segment| segment.parse().ok())
+            .ok_or_else(invalid)?;
+
+        let repo = segments[..marker]
+            .iter()
+            .filter(|segment| **segment != "-")
+            .copied()
+            .collect::<Vec<_>>()
+            .join("/");
+
+        Ok(PullNumber {
+            number,
+            repo: Some(repo),
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Open,
+    Closed,
+    Merged,
+}
+
+/// A change of state of a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Close,
+    Reopen,
+    Ready,
+    Draft,
+}
+
+impl std::fmt::Display for Transition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transition::Close => write!(f, "closed")?,
+            Transition::Reopen => write!(f, "reopened")?,
+            Transition::Ready => write!(f, "marked ready for review")?,
+            Transition::Draft => write!(f, "converted to draft")?,
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateFilter {
+    Open,
+    Closed,
+    Merged,
+    All,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ListFilter {
+    /// Only pull requests opened by this user
+    #[clap(short, long)]
+    pub author: Option<String>,
+
+    /// Only pull requests from this branch
+    #[clap(long)]
+    pub head: Option<String>,
+
+    /// Only pull requests into this branch
+    #[clap(long)]
+    pub base: Option<String>,
+
+    /// Only pull requests with this label (repeatable)
+    #[clap(short, long = "label")]
+    pub labels: Vec<String>,
+
+    /// Only pull requests in this state
+    #[clap(short, long, value_enum, default_value_t = StateFilter::Open)]
+    pub state: StateFilter,
+
+    /// Only draft pull requests, or only ready ones with --draft=false
+    #[clap(long, num_args = 0..=1, default_missing_value = "true")]
+    pub draft: Option<bool>,
+
+    /// Maximum number of pull requests to list
+    #[clap(short = 'n', long, default_value_t = 30)]
+    pub limit: usize,
+}
+
+impl StateFilter {
+    pub fn matches(&self, state: State) -> bool {
+        match self {
+            StateFilter::Open => state == State::Open,
+            StateFilter::Closed => state == State::Closed,
+            StateFilter::Merged => state == State::Merged,
+            StateFilter::All => true,
+        }
+    }
+}
+
+impl ListFilter {
+    /// Check a pull request against every filter,
+    /// drivers use this for whatever their api can't filter on
+    pub fn matches(&self, pr: &PullRequest) -> bool {
+        if !self.state.matches(pr.state) {
+            return false;
+        }
+
+        if let Some(author) = &self.author {
+            if !pr.author.eq_ignore_ascii_case(author) {
+                return false;
+            }
+        }
+
+        if let Some(head) = &self.head {
+            let branch = head.rsplit_once(':').map(|(_, b)| b).unwrap_or(head);
+            if pr.head != branch {
+                return false;
+            }
+        }
+
+        if let Some(base) = &self.base {
+            if &pr.base != base {
+                return false;
+            }
+        }
+
+        if let Some(draft) = self.draft {
+            if pr.draft != draft {
+                return false;
+            }
+        }
+
+        self.labels.iter().all(|label| pr.labels.contains(label))
+    }
+}
+
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            State::Open => write!(f, "open")?,
+            State::Closed => write!(f, "closed")?,
+            State::Merged => write!(f, "merged")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl CheckState {
+    /// Whether the check finished without succeeding
+    pub fn failed(&self) -> bool {
+        matches!(self, CheckState::Failure | CheckState::Cancelled)
+    }
+}
+
+impl std::fmt::Display for CheckState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckState::Pending => write!(f, "pending")?,
+            CheckState::Success => write!(f, "success")?,
+            CheckState::Failure => write!(f, "failure")?,
+            CheckState::Cancelled => write!(f, "cancelled")?,
+            CheckState::Skipped => write!(f, "skipped")?,
+            CheckState::Neutral => write!(f, "neutral")?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Render a pull request for the terminal
+pub fn details(details: &Details) -> String {
+    let pr = &details.pull;
+    let tty = std::io::stdout().is_terminal();
+
+    let state = if pr.draft && pr.state == State::Open {
+        "draft".to_string()
+    } else {
+        pr.state.to_string()
+    };
+
+    let mut out = String::new();
+    out.push_str(&format!("{} #{}\n", bold(&pr.title, tty), pr.number));
+    out.push_str(&format!(
+        "{state} - {} wants to merge {} into {}\n",
+        pr.author, pr.head, pr.base
+    ));
+
+    if !pr.labels.is_empty() {
+        out.push_str(&format!("labels: {}\n", pr.labels.join(", ")));
+    }
+
+    if !details.reviewers.is_empty() {
+        let reviewers: Vec<_> = details
+            .reviewers
+            .iter()
+            .map(|r| format!("{} ({})", r.name, r.state))
+            .collect();
+        out.push_str(&format!("reviewers: {}\n", reviewers.join(", ")));
+    }
+
+    if !details.checks.is_empty() {
+        let passed = details
+            .checks
+            .iter()
+            .filter(|c| c.state == CheckState::Success)
+            .count();
+        out.push_str(&format!(
+            "checks: {passed}/{} passed\n",
+            details.checks.len()
+        ));
+
+        for check in &details.checks {
+            out.push_str(&format!("  {:<9} {}\n", check.state, check.name));
+        }
+    }
+
+    match (details.mergeable, &details.merge_state) {
+        (Some(true), Some(state)) => out.push_str(&format!("mergeable ({state})\n")),
+        (Some(true), None) => out.push_str("mergeable\n"),
+        (Some(false), Some(state)) => out.push_str(&format!("not mergeable ({state})\n")),
+        (Some(false), None) => out.push_str("not mergeable\n"),
+        (None, _) => (),
+    }
+
+    let body = markdown(&details.body, tty);
+    if !body.is_empty() {
+        out.push('\n');
+        for line in body.lines() {
+            out.push_str(format!("  {line}").trim_end());
+            out.push('\n');
+        }
+    }
+
+    out.push_str(&format!("\n{}\n", pr.url));
+    out
+}
+
+pub(super) fn bold(s: &str, tty: bool) -> String {
+    if tty {
+        format!("\x1b[1m{s}\x1b[0m")
+    } else {
+        s.to_string()
+    }
+}
+
+/// Just enough markdown for a terminal: hidden comments dropped, headings emphasized
+pub(super) fn markdown(body: &str, tty: bool) -> String {
+    let mut text = body.replace("\r\n", "\n");
+
+    while let Some(start) = text.find("<!--") {
+        let Some(end) = text[start..].find("-->") else {
+            break;
+        };
+        text.replace_range(start..start + end + 3, "");
+    }
+
+    let mut out = Vec::new();
+    let mut blank = false;
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !blank && !out.is_empty() {
+                out.push(String::new());
+            }
+            blank = true;
+            continue;
+        }
+        blank = false;
+
+        let heading = line.trim_start_matches('#');
+        if heading.len() < line.len() && heading.starts_with(' ') {
+            out.push(bold(heading.trim(), tty));
+        } else {
+            out.push(line.to_string());
+        }
+    }
+
+    out.join("\n").trim_end().to_string()
+}
+
+/// Render rows with their columns padded to the widest cell, two spaces apart
+fn aligned<const N: usize>(rows: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Render checks as an aligned table
+pub fn checks(checks: &[Check]) -> String {
+    let rows: Vec<[String; 4]> = checks
+        .iter()
+        .map(|check| {
+            let duration = check
+                .duration
+                .map(|secs| humantime::format_duration(Duration::from_secs(secs)).to_string())
+                .unwrap_or_default();
+
+            [
+                check.state.to_string(),
+                check.name.clone(),
+                duration,
+                check.url.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    aligned(&rows)
+}
+
+/// Render pull requests as an aligned table
+pub fn table(prs: &[PullRequest]) -> String {
+    let rows: Vec<[String; 5]> = prs
+        .iter()
+        .map(|pr| {
+            let state = if pr.draft && pr.state == State::Open {
+                "draft".to_string()
+            } else {
+                pr.state.to_string()
+            };
+
+            [
+                format!("#{}", pr.number),
+                pr.title.clone(),
+                format!("{} -> {}", pr.head, pr.base),
+                pr.author.clone(),
+                state,
+            ]
+        })
+        .collect();
+
+    aligned(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aligned, PullNumber};
+
+    fn parse(s: &str) -> (u64, Option<String>) {
+        let number: PullNumber = s.parse().unwrap();
+        (number.number, number.repo)
+    }
+
+    #[test]
+    fn bare_number() {
+        assert_eq!(parse("12"), (12, None));
+        assert_eq!(parse("#12"), (12, None));
+    }
+
+    #[test]
+    fn urls() {
+        let repo = Some("owner/repo".to_string());
+        assert_eq!(
+            parse("https://github.com/owner/repo/pull/12"),
+            (12, repo.clone())
+        );
+        assert_eq!(
+            parse("https://github.com/owner/repo/pull/12/files#diff"),
+            (12, repo.clone())
+        );
+        assert_eq!(
+            parse("https://gitea.com/owner/repo/pulls/12?tab=files"),
+            (12, repo)
+        );
+        assert_eq!(
+            parse("https://gitlab.com/group/sub/project/-/merge_requests/12"),
+            (12, Some("group/sub/project".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!("twelve".parse::<PullNumber>().is_err());
+        assert!("https://github.com/owner/repo/issues/12"
+            .parse::<PullNumber>()
+            .is_err());
+        assert!("https://github.com/owner/repo/pull/latest"
+            .parse::<PullNumber>()
+            .is_err());
+    }
+
+    #[test]
+    fn belongs_to() {
+        let number: PullNumber = "https://github.com/Owner/Repo/pull/12".parse().unwrap();
+        assert!(number.belongs_to("owner/repo"));
+        assert!(!number.belongs_to("fork/repo"));
+
+        let number: PullNumber = "12".parse().unwrap();
+        assert!(number.belongs_to("fork/repo"));
+    }
+
+    #[test]
+    fn aligned_columns() {
+        let rows = [
+            [
+                "#1".to_string(),
+                "Add parser".to_string(),
+                "open".to_string(),
+            ],
+            ["#12".to_string(), "Fix".to_string(), String::new()],
+        ];
+
+        assert_eq!(aligned(&rows), "#1   Add parser  open\n#12  Fix\n");
+    }
+}
@@ -0,0 +1,82 @@
+use std::process::Command;
+
+use gix::Repository;
+
+use crate::errors::Error;
+
+use super::{Common, Req};
+
+#[derive(Debug)]
+pub enum PushError {
+    Spawn(std::io::Error),
+    Rejected(std::process::ExitStatus),
+}
+
+impl std::fmt::Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::Spawn(err) => write!(f, "failed to run git push: {err}")?,
+            PushError::Rejected(status) => write!(f, "git push failed: {status}")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for PushError {}
+
+impl Error for PushError {
+    fn status(&self) -> i32 {
+        11
+    }
+}
+
+impl Req {
+    /// Push the from branch to the from remote and track it,
+    /// if requested by `--push` or `tidal.push`
+    pub fn push(&self, repo: &Repository, opts: &Common) -> Result<(), PushError> {
+        if !should_push(repo, opts) {
+            return Ok(());
+        }
+
+        self.push_branch(repo, opts.force_with_lease)
+    }
+
+    /// Push the from branch to the from remote and track it
+    pub fn push_branch(&self, repo: &Repository, force_with_lease: bool) -> Result<(), PushError> {
+        let mut cmd = Command::new("git");
+        cmd.current_dir(repo.work_dir().unwrap_or(repo.git_dir()))
+            .arg("push")
+            .arg("--set-upstream");
+
+        if force_with_lease {
+            cmd.arg("--force-with-lease");
+        }
+
+        let status = cmd
+            .arg(&self.from.remote)
+            .arg(&self.from.branch)
+            .status()
+            .map_err(PushError::Spawn)?;
+
+        if !status.success() {
+            return Err(PushError::Rejected(status));
+        }
+
+        Ok(())
+    }
+}
+
+fn should_push(repo: &Repository, opts: &Common) -> bool {
+    if opts.push {
+        return true;
+    }
+
+    if opts.no_push {
+        return false;
+    }
+
+    repo.config_snapshot()
+        .boolean("tidal.push")
+        .unwrap_or(false)
+}
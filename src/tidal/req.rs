@@ -2,7 +2,7 @@ use gix::{config::File, Remote, Repository};
 
 use crate::errors::Error;
 
-use super::Common;
+use super::Refs;
 
 #[derive(Debug)]
 pub enum ReqError {
@@ -39,13 +39,14 @@ impl Error for ReqError {
     }
 }
 
+#[derive(Clone)]
 pub struct Req {
     pub from: Ref,
     pub to: Ref,
 }
 
 impl Req {
-    pub fn overwrite(&mut self, opts: &Common) {
+    pub fn overwrite(&mut self, opts: &Refs) {
         if let Some(b) = &opts.from_branch {
             self.from.branch = b.to_string();
         }
@@ -109,6 +110,7 @@ impl Req {
     }
 }
 
+#[derive(Clone)]
 pub struct Ref {
     pub branch: String,
     pub remote: String,
@@ -0,0 +1,192 @@
+use std::{fs, io, path::Path};
+
+use crate::errors::Error;
+
+use super::Desc;
+
+/// Verdict of a review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+/// A review to submit on a pull request
+#[derive(Debug, Clone)]
+pub struct Review {
+    pub verdict: Verdict,
+    pub body: String,
+    pub comments: Vec<LineComment>,
+}
+
+/// A comment on a line of the new version of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineComment {
+    pub path: String,
+    pub line: u64,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub enum ReviewError {
+    Description(io::Error),
+    Comments(io::Error),
+    Malformed(usize),
+    NoBody,
+}
+
+impl std::fmt::Display for ReviewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewError::Description(err) => write!(f, "failed to read review: {err}")?,
+            ReviewError::Comments(err) => write!(f, "failed to read line comments: {err}")?,
+            ReviewError::Malformed(line) => {
+                write!(f, "line comment {line} is not `path:line: text`")?
+            }
+            ReviewError::NoBody => write!(
+                f,
+                "requesting changes or commenting needs a body, see --body"
+            )?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ReviewError {}
+
+impl Error for ReviewError {
+    fn status(&self) -> i32 {
+        18
+    }
+}
+
+impl Review {
+    pub fn build(
+        verdict: Verdict,
+        body: Option<&Desc>,
+        comments: Option<&Path>,
+    ) -> Result<Review, ReviewError> {
+        let body = match body {
+            Some(desc) => desc.read().map_err(ReviewError::Description)?,
+            None => String::new(),
+        };
+
+        if verdict != Verdict::Approve && body.trim().is_empty() {
+            return Err(ReviewError::NoBody);
+        }
+
+        let comments = match comments {
+            Some(path) => {
+                let text = fs::read_to_string(path).map_err(ReviewError::Comments)?;
+                line_comments(&text)?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Review {
+            verdict,
+            body,
+            comments,
+        })
+    }
+}
+
+/// Parse line comments, one `path:line: text` per line.
+///
+/// Indented lines continue the previous comment and lines starting with `#` are ignored.
+fn line_comments(text: &str) -> Result<Vec<LineComment>, ReviewError> {
+    let mut comments: Vec<LineComment> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            let Some(last) = comments.last_mut() else {
+                return Err(ReviewError::Malformed(i + 1));
+            };
+
+            last.body.push('\n');
+            last.body.push_str(line.trim());
+            continue;
+        }
+
+        let mut parts = line.splitn(3, ':');
+        let (Some(path), Some(number), Some(body)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ReviewError::Malformed(i + 1));
+        };
+
+        let Ok(number) = number.trim().parse() else {
+            return Err(ReviewError::Malformed(i + 1));
+        };
+
+        comments.push(LineComment {
+            path: path.to_string(),
+            line: number,
+            body: body.trim().to_string(),
+        });
+    }
+
+    Ok(comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_comments, LineComment, ReviewError};
+
+    fn comment(path: &str, line: u64, body: &str) -> LineComment {
+        LineComment {
+            path: path.to_string(),
+            line,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn single_line() {
+        let comments = line_comments("src/main.rs:12: this can panic\n").unwrap();
+        assert_eq!(comments, vec![comment("src/main.rs", 12, "this can panic")]);
+    }
+
+    #[test]
+    fn colons_in_text() {
+        let comments = line_comments("a.rs:3: use std::io: it's shorter").unwrap();
+        assert_eq!(
+            comments,
+            vec![comment("a.rs", 3, "use std::io: it's shorter")]
+        );
+    }
+
+    #[test]
+    fn continuation_and_comments() {
+        let text = "# review of the parser\n\na.rs:1: first\n  second\n\tthird\nb.rs:2: other\n";
+        let comments = line_comments(text).unwrap();
+        assert_eq!(
+            comments,
+            vec![
+                comment("a.rs", 1, "first\nsecond\nthird"),
+                comment("b.rs", 2, "other"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(matches!(
+            line_comments("a.rs: no line"),
+            Err(ReviewError::Malformed(1))
+        ));
+        assert!(matches!(
+            line_comments("a.rs:1: ok\nb.rs:x: bad"),
+            Err(ReviewError::Malformed(2))
+        ));
+        assert!(matches!(
+            line_comments("  dangling continuation"),
+            Err(ReviewError::Malformed(1))
+        ));
+    }
+}
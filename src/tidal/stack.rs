@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use gix::{ObjectId, Repository};
+
+use super::{
+    history::{git_error, range, LogError},
+    Ref, Req,
+};
+
+const NAV_START: &str = "<!-- tidal:stack -->";
+const NAV_END: &str = "<!-- /tidal:stack -->";
+
+/// Branches stacked on top of each other, each based on the one below it
+#[derive(Debug, Clone)]
+pub struct Stack {
+    /// Branch the bottom of the stack merges into
+    pub base: String,
+    /// Branches from the bottom of the stack up
+    pub branches: Vec<String>,
+}
+
+impl Req {
+    /// The stack of local branches the from branch is part of.
+    ///
+    /// A branch is stacked on the nearest local branch whose tip is in its history
+    /// but not in the to branch (as last fetched from its remote, falling back to the
+    /// local branch), the stack continues upwards while a single branch
+    /// is stacked on its top.
+    pub fn stack(&self, repo: &Repository) -> Result<Stack, LogError> {
+        // what was already merged upstream, even if the local branch lags behind
+        let tracking = format!("refs/remotes/{}/{}", self.to.remote, self.to.branch);
+        let to = match repo.rev_parse_single(tracking.as_str()) {
+            Ok(id) => id.detach(),
+            Err(_) => repo
+                .rev_parse_single(self.to.branch.as_str())
+                .map_err(git_error)?
+                .detach(),
+        };
+
+        let mut tips = Vec::new();
+        let references = repo.references().map_err(git_error)?;
+        for reference in references.local_branches().map_err(git_error)? {
+            let reference = reference.map_err(LogError::Git)?;
+            let name = reference.name().shorten().to_string();
+
+            if name == self.to.branch {
+                continue;
+            }
+
+            if let Some(id) = reference.target().try_id() {
+                tips.push((name, id.to_owned()));
+            }
+        }
+
+        let mut parents = HashMap::new();
+        for (name, tip) in &tips {
+            parents.insert(name.clone(), parent(repo, to, &tips, *tip)?);
+        }
+        let parent_of = |branch: &str| parents.get(branch).cloned().flatten();
+
+        let mut branches = vec![self.from.branch.clone()];
+        while let Some(parent) = parent_of(branches.last().unwrap()) {
+            if branches.contains(&parent) {
+                break;
+            }
+            branches.push(parent);
+        }
+        branches.reverse();
+
+        loop {
+            let top = branches.last().unwrap().clone();
+
+            let mut children = Vec::new();
+            for (name, _) in &tips {
+                if branches.contains(name) {
+                    continue;
+                }
+
+                if parent_of(name).as_ref() == Some(&top) {
+                    children.push(name.clone());
+                }
+            }
+
+            match children.as_slice() {
+                [child] => branches.push(child.clone()),
+                _ => break,
+            }
+        }
+
+        Ok(Stack {
+            base: self.to.branch.clone(),
+            branches,
+        })
+    }
+
+    /// The request of a single branch of a stack onto the branch below it
+    pub fn layer(&self, branch: &str, base: &str) -> Req {
+        Req {
+            from: Ref {
+                branch: branch.to_string(),
+                remote: self.from.remote.clone(),
+            },
+            to: Ref {
+                branch: base.to_string(),
+                remote: self.to.remote.clone(),
+            },
+        }
+    }
+}
+
+/// The branch whose tip is nearest in the history of `tip` that isn't in `to`, if any
+fn parent(
+    repo: &Repository,
+    to: ObjectId,
+    tips: &[(String, ObjectId)],
+    tip: ObjectId,
+) -> Result<Option<String>, LogError> {
+    for id in range(repo, tip, to)? {
+        // branches sharing the tip are the same layer
+        if id == tip {
+            continue;
+        }
+
+        if let Some((name, _)) = tips.iter().find(|(_, tip)| *tip == id) {
+            return Ok(Some(name.clone()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// `body` with the navigation of a stack of pull requests (bottom first) replaced or appended
+pub fn with_navigation(body: &str, base: &str, numbers: &[u64], current: u64) -> String {
+    let mut nav = format!("{NAV_START}\n**Stack**\n\n");
+    for number in numbers.iter().rev() {
+        if *number == current {
+            nav.push_str(&format!("- #{number} 👈\n"));
+        } else {
+            nav.push_str(&format!("- #{number}\n"));
+        }
+    }
+    nav.push_str(&format!("- `{base}`\n{NAV_END}"));
+
+    let start = body.find(NAV_START);
+    let end = body.find(NAV_END).map(|end| end + NAV_END.len());
+
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{nav}{}", &body[..start], &body[end..])
+        }
+        _ if body.trim().is_empty() => nav,
+        _ => format!("{}\n\n{nav}", body.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{with_navigation, NAV_END, NAV_START};
+    use crate::tidal::fixture::Fixture;
+
+    /// `main` with `a`, `b` stacked on it and `c` on `b`
+    fn linear() -> Fixture {
+        let fixture = Fixture::new();
+        fixture.commit("base");
+        for branch in ["a", "b", "c"] {
+            fixture.git(&["switch", "-q", "-c", branch]);
+            fixture.commit(branch);
+        }
+
+        fixture
+    }
+
+    fn branches(fixture: &Fixture, from: &str) -> Vec<String> {
+        fixture.req(from).stack(&fixture.repo()).unwrap().branches
+    }
+
+    #[test]
+    fn linear_stack() {
+        let fixture = linear();
+
+        for from in ["a", "b", "c"] {
+            assert_eq!(branches(&fixture, from), ["a", "b", "c"]);
+        }
+    }
+
+    #[test]
+    fn fork_in_stack() {
+        let fixture = linear();
+        fixture.git(&["switch", "-q", "-c", "b2", "a"]);
+        fixture.commit("b2");
+
+        // two branches on a, the stack above it is ambiguous
+        assert_eq!(branches(&fixture, "a"), ["a"]);
+        assert_eq!(branches(&fixture, "b"), ["a", "b", "c"]);
+        assert_eq!(branches(&fixture, "b2"), ["a", "b2"]);
+    }
+
+    #[test]
+    fn merged_layer() {
+        let fixture = linear();
+        fixture.git(&["switch", "-q", "main"]);
+        fixture.git(&["merge", "-q", "--ff-only", "a"]);
+
+        assert_eq!(branches(&fixture, "c"), ["b", "c"]);
+    }
+
+    #[test]
+    fn navigation_in_empty_body() {
+        let nav = with_navigation("", "main", &[1, 2], 2);
+
+        assert_eq!(
+            nav,
+            format!("{NAV_START}\n**Stack**\n\n- #2 👈\n- #1\n- `main`\n{NAV_END}")
+        );
+    }
+
+    #[test]
+    fn navigation_appended() {
+        let nav = with_navigation("Adds a parser.\n", "main", &[1], 1);
+
+        assert!(nav.starts_with("Adds a parser.\n\n<!-- tidal:stack -->"));
+        assert!(nav.ends_with(NAV_END));
+    }
+
+    #[test]
+    fn navigation_rerendered() {
+        let first = with_navigation("Adds a parser.\n\nThanks!", "main", &[1], 1);
+        let body = format!("{first}\n\nA note after the stack.");
+        let second = with_navigation(&body, "main", &[1, 2], 1);
+
+        assert_eq!(second.matches(NAV_START).count(), 1);
+        assert!(second.contains("- #2\n- #1 👈\n"));
+        assert!(second.starts_with("Adds a parser.\n\nThanks!\n\n"));
+        assert!(second.ends_with(&format!("{NAV_END}\n\nA note after the stack.")));
+    }
+}